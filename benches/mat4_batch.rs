@@ -0,0 +1,69 @@
+// Run with cargo bench --bench mat4_batch. Uses a plain timing loop, so it
+// needs no extra dependencies.
+
+use render_math::{
+    mat::{
+        batch::{mul_batch, mul_batch_column_major, write_column_major},
+        colmat4f32::ColMat4f32,
+        mat4f32::Mat4f32,
+    },
+    rotor::rot3df32::Rot3Df32,
+    vec::vec3::Vec3f32,
+};
+use std::{hint::black_box, time::Instant};
+
+const INSTANCES: usize = 10_000;
+const ROUNDS: u32 = 200;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    // Warm up the caches and the branch predictors
+    for _ in 0..ROUNDS / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let per_matrix = start.elapsed().as_secs_f64() * 1e9 / (ROUNDS as f64 * INSTANCES as f64);
+    println!("{name:<28} {per_matrix:>7.2} ns per matrix");
+}
+
+fn main() {
+    let parent = Mat4f32::view_from_pose(
+        Vec3f32::new(1.0, 2.0, 3.0),
+        &Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), 0.4),
+    );
+    let locals: Vec<Mat4f32> = (0..INSTANCES)
+        .map(|i| {
+            let rotation = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 0.0, 1.0), i as f32);
+            Mat4f32::view_from_pose(Vec3f32::new(i as f32, 0.0, -1.0), &rotation)
+        })
+        .collect();
+    let mut out = vec![Mat4f32::zero(); INSTANCES];
+    let mut columns = vec![ColMat4f32::zero(); INSTANCES];
+
+    bench("mul loop", || {
+        for (local, out) in locals.iter().zip(out.iter_mut()) {
+            *out = black_box(&parent) * local;
+        }
+        black_box(&out);
+    });
+    bench("mul loop + transpose", || {
+        for (local, out) in locals.iter().zip(columns.iter_mut()) {
+            *out = ColMat4f32::from(black_box(&parent) * local);
+        }
+        black_box(&columns);
+    });
+    bench("mul_batch", || {
+        mul_batch(black_box(&parent), &locals, &mut out);
+        black_box(&out);
+    });
+    bench("mul_batch_column_major", || {
+        mul_batch_column_major(black_box(&parent), &locals, &mut columns);
+        black_box(&columns);
+    });
+    bench("write_column_major", || {
+        write_column_major(black_box(&locals), &mut columns);
+        black_box(&columns);
+    });
+}
@@ -0,0 +1,62 @@
+// Run with cargo bench --bench ops. Compares operators on values with the
+// same operators on references in a typical hot transform loop.
+#![allow(clippy::op_ref)]
+
+use render_math::{mat::mat4f32::Mat4f32, rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32};
+use std::{hint::black_box, time::Instant};
+
+const COUNT: usize = 10_000;
+const ROUNDS: u32 = 200;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    for _ in 0..ROUNDS / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let per_item = start.elapsed().as_secs_f64() * 1e9 / (ROUNDS as f64 * COUNT as f64);
+    println!("{name:<32} {per_item:>7.2} ns per item");
+}
+
+fn main() {
+    let view_proj = Mat4f32::perspective(1.0, 1.5, 0.1, 100.0)
+        * Mat4f32::view_from_pose(Vec3f32::new(1.0, 2.0, 3.0), &Rot3Df32::identity());
+    let models: Vec<Mat4f32> = (0..COUNT)
+        .map(|i| {
+            let rotation = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), i as f32);
+            Mat4f32::view_from_pose(Vec3f32::new(i as f32, 0.0, -5.0), &rotation)
+        })
+        .collect();
+    let points: Vec<Vec3f32> = (0..COUNT)
+        .map(|i| Vec3f32::new(i as f32, 1.0, -2.0))
+        .collect();
+    let mut out = vec![Mat4f32::zero(); COUNT];
+    let mut moved = vec![Vec3f32::new(0.0, 0.0, 0.0); COUNT];
+
+    bench("mat4 * mat4 (values)", || {
+        for (model, out) in models.iter().zip(out.iter_mut()) {
+            *out = black_box(view_proj) * *model;
+        }
+        black_box(&out);
+    });
+    bench("&mat4 * &mat4 (references)", || {
+        for (model, out) in models.iter().zip(out.iter_mut()) {
+            *out = black_box(&view_proj) * model;
+        }
+        black_box(&out);
+    });
+    bench("vec3 + vec3 * f32 (values)", || {
+        for (p, out) in points.iter().zip(moved.iter_mut()) {
+            *out = *p + *p * black_box(0.5);
+        }
+        black_box(&moved);
+    });
+    bench("&vec3 + &vec3 * f32 (references)", || {
+        for (p, out) in points.iter().zip(moved.iter_mut()) {
+            *out = p + &(p * black_box(0.5));
+        }
+        black_box(&moved);
+    });
+}
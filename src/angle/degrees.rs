@@ -0,0 +1,54 @@
+use crate::angle::radians::Radians;
+
+/// An angle in degrees
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Degrees(pub f32);
+
+impl Degrees {
+    pub fn to_radians(self) -> Radians {
+        Radians(self.0.to_radians())
+    }
+
+    /// Returns the angle wrapped into -180..180
+    pub fn wrapped(self) -> Self {
+        self.to_radians().wrapped().to_degrees()
+    }
+
+    /// Returns the signed difference other - self along the shorter way
+    /// around the circle
+    pub fn shortest_delta(self, other: Self) -> Self {
+        self.to_radians()
+            .shortest_delta(other.to_radians())
+            .to_degrees()
+    }
+
+    /// Interpolates to other along the shorter way around the circle
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Degrees(self.0 + self.shortest_delta(other).0 * t)
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(radians: Radians) -> Self {
+        radians.to_degrees()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degrees() {
+        assert!((-170.001..-169.999).contains(&Degrees(190.0).wrapped().0));
+        let delta = Degrees(350.0).shortest_delta(Degrees(10.0));
+        assert!((19.999..20.001).contains(&delta.0));
+        let mid = Degrees(350.0).lerp(Degrees(10.0), 0.5);
+        assert!((359.999..360.001).contains(&mid.0));
+
+        let radians: Radians = Degrees(180.0).into();
+        assert!(
+            (std::f32::consts::PI - 0.0001..std::f32::consts::PI + 0.0001).contains(&radians.0)
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod degrees;
+pub mod radians;
+pub mod wrap;
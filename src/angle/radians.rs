@@ -0,0 +1,33 @@
+use crate::angle::{degrees::Degrees, wrap};
+
+/// An angle in radians
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Radians(pub f32);
+
+impl Radians {
+    pub fn to_degrees(self) -> Degrees {
+        Degrees(self.0.to_degrees())
+    }
+
+    /// Returns the angle wrapped into -PI..PI
+    pub fn wrapped(self) -> Self {
+        Radians(wrap::wrap_angle(self.0))
+    }
+
+    /// Returns the signed difference other - self along the shorter way
+    /// around the circle
+    pub fn shortest_delta(self, other: Self) -> Self {
+        Radians(wrap::shortest_angle_delta(self.0, other.0))
+    }
+
+    /// Interpolates to other along the shorter way around the circle
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Radians(wrap::lerp_angle(self.0, other.0, t))
+    }
+}
+
+impl From<Degrees> for Radians {
+    fn from(degrees: Degrees) -> Self {
+        degrees.to_radians()
+    }
+}
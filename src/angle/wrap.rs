@@ -0,0 +1,49 @@
+use std::f32::consts::{PI, TAU};
+
+/// Wraps an angle in radians into -PI..PI
+pub fn wrap_angle(angle: f32) -> f32 {
+    let wrapped = (angle + PI).rem_euclid(TAU) - PI;
+    // rem_euclid can round up to TAU for tiny negative inputs
+    if wrapped >= PI {
+        wrapped - TAU
+    } else {
+        wrapped
+    }
+}
+
+/// Returns the signed difference b - a in radians along the shorter way
+/// around the circle, in -PI..PI
+pub fn shortest_angle_delta(a: f32, b: f32) -> f32 {
+    wrap_angle(b - a)
+}
+
+/// Interpolates between the angles a and b (in radians) along the shorter
+/// way around the circle. The result is not wrapped.
+pub fn lerp_angle(a: f32, b: f32, t: f32) -> f32 {
+    a + shortest_angle_delta(a, b) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_angle() {
+        assert!((-0.0001..0.0001).contains(&wrap_angle(TAU)));
+        assert!((-0.0001..0.0001).contains(&wrap_angle(-3.0 * TAU)));
+        assert!((-PI - 0.0001..-PI + 0.0001).contains(&wrap_angle(PI)));
+        assert!((-1.0001..-0.9999).contains(&wrap_angle(TAU - 1.0)));
+        assert!((0.9999..1.0001).contains(&wrap_angle(1.0 - 2.0 * TAU)));
+    }
+
+    #[test]
+    fn test_shortest_delta_and_lerp() {
+        // Across the -PI/PI boundary
+        let a = PI - 0.1;
+        let b = -PI + 0.1;
+        assert!((0.1999..0.2001).contains(&shortest_angle_delta(a, b)));
+        assert!((-0.2001..-0.1999).contains(&shortest_angle_delta(b, a)));
+        let mid = wrap_angle(lerp_angle(a, b, 0.5));
+        assert!((PI - 0.0001..PI + 0.0001).contains(&mid.abs()));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod smooth_damp;
+pub mod track;
@@ -0,0 +1,92 @@
+use crate::{rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32};
+use std::ops::{Add, Mul, Sub};
+
+/// Moves current towards target like a critically damped spring, so it
+/// arrives smoothly without overshooting. Unlike lerping by a fixed factor
+/// every frame, the result does not depend on the frame rate.
+/// velocity is the state of the spring, keep it between calls (start with 0).
+/// smooth_time is roughly the time it takes to reach the target.
+/// Works for f32, Vec3f32 and every other type supporting these operations.
+pub fn smooth_damp<T>(current: T, target: T, velocity: &mut T, smooth_time: f32, dt: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    let omega = 2.0 / smooth_time.max(1e-4);
+    // Approximation of e^(-omega * dt)
+    let x = omega * dt;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+    let change = current - target;
+    let temp = (*velocity + change * omega) * dt;
+    *velocity = (*velocity - temp * omega) * decay;
+    target + (change + temp) * decay
+}
+
+/// smooth_damp for rotations. The remaining rotation is damped in log space
+/// (as a rotation vector), so the rotor always takes the shortest way.
+/// angular_velocity is a rotation vector per second in world space, keep it
+/// between calls (start with 0).
+pub fn smooth_damp_rotor(
+    current: Rot3Df32,
+    target: Rot3Df32,
+    angular_velocity: &mut Vec3f32,
+    smooth_time: f32,
+    dt: f32,
+) -> Rot3Df32 {
+    let remaining = target.appended(current.inverted()).to_scaled_axis();
+    let zero = Vec3f32::new(0.0, 0.0, 0.0);
+    let step = smooth_damp(zero, remaining, angular_velocity, smooth_time, dt);
+    let mut result = Rot3Df32::from_scaled_axis(step).appended(current);
+    result.normalize();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_rate_independent() {
+        let mut slow = 0.0;
+        let mut slow_velocity = 0.0;
+        for _ in 0..30 {
+            slow = smooth_damp(slow, 10.0, &mut slow_velocity, 0.3, 1.0 / 30.0);
+        }
+        let mut fast = 0.0;
+        let mut fast_velocity = 0.0;
+        for _ in 0..144 {
+            fast = smooth_damp(fast, 10.0, &mut fast_velocity, 0.3, 1.0 / 144.0);
+        }
+        assert!((slow - fast).abs() < 0.05);
+        assert!(slow > 9.5 && slow <= 10.0);
+
+        let mut v = Vec3f32::new(0.0, 0.0, 0.0);
+        let mut velocity = Vec3f32::new(0.0, 0.0, 0.0);
+        let target = Vec3f32::new(1.0, -2.0, 3.0);
+        for _ in 0..600 {
+            v = smooth_damp(v, target, &mut velocity, 0.2, 1.0 / 60.0);
+        }
+        assert!((v - target).magnitude() < 0.0001);
+    }
+
+    #[test]
+    fn test_smooth_damp_rotor() {
+        let axis = Vec3f32::new(0.0, 1.0, 0.0);
+        let target = Rot3Df32::from_axis_angle(axis, 2.0);
+        let mut current = Rot3Df32::identity();
+        let mut velocity = Vec3f32::new(0.0, 0.0, 0.0);
+
+        for _ in 0..10 {
+            current = smooth_damp_rotor(current, target, &mut velocity, 0.5, 1.0 / 60.0);
+        }
+        let angle = current.to_scaled_axis();
+        assert!(angle.y > 0.0 && angle.y < 2.0);
+        assert!(angle.x.abs() < 0.0001 && angle.z.abs() < 0.0001);
+
+        for _ in 0..600 {
+            current = smooth_damp_rotor(current, target, &mut velocity, 0.5, 1.0 / 60.0);
+        }
+        let remaining = target.appended(current.inverted()).to_scaled_axis();
+        assert!(remaining.magnitude() < 0.0001);
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    rotor::rot3df32::Rot3Df32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// Values that a Track can blend between
+pub trait Interpolate: Copy {
+    /// Returns self for t = 0 and other for t = 1. Cubic tracks also pass
+    /// t outside of 0..1 and expect an extrapolation.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for Vec2f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Interpolate for Vec3f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+/// Interpolates with Rot3Df32::slerp
+impl Interpolate for Rot3Df32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.slerp(*other, t)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Holds the value of the previous keyframe
+    Step,
+    Linear,
+    /// Smooth curve through all keyframes (Catmull-Rom)
+    Cubic,
+}
+
+/// What happens when sampling outside of the time range of the keyframes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Looping {
+    /// Holds the first or last value
+    Clamp,
+    /// Starts over at the first keyframe
+    Repeat,
+    /// Plays forwards and backwards in turn
+    PingPong,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+}
+
+/// An animation curve of keyframes (in seconds), sorted by time
+#[derive(Debug, Clone)]
+pub struct Track<T> {
+    keyframes: Vec<Keyframe<T>>,
+    pub interpolation: Interpolation,
+    pub looping: Looping,
+}
+
+impl<T: Interpolate> Track<T> {
+    /// Returns a track without keyframes
+    pub fn new(interpolation: Interpolation, looping: Looping) -> Self {
+        Self {
+            keyframes: Vec::new(),
+            interpolation,
+            looping,
+        }
+    }
+
+    /// Returns a track of the keyframes, which are sorted by time. Of
+    /// several keyframes at the same time the last one is kept, like insert
+    /// does.
+    pub fn from_keyframes(
+        mut keyframes: Vec<Keyframe<T>>,
+        interpolation: Interpolation,
+        looping: Looping,
+    ) -> Self {
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        keyframes.dedup_by(|later, kept| {
+            let same_time = later.time == kept.time;
+            if same_time {
+                kept.value = later.value;
+            }
+            same_time
+        });
+        Self {
+            keyframes,
+            interpolation,
+            looping,
+        }
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<T>] {
+        &self.keyframes
+    }
+
+    /// Adds a keyframe, replacing the keyframe at the same time if there is one
+    pub fn insert(&mut self, time: f32, value: T) {
+        let index = self.keyframes.partition_point(|k| k.time < time);
+        match self.keyframes.get_mut(index) {
+            Some(key) if key.time == time => key.value = value,
+            _ => self.keyframes.insert(index, Keyframe { time, value }),
+        }
+    }
+
+    /// Returns the time between the first and the last keyframe
+    pub fn duration(&self) -> f32 {
+        match (self.keyframes.first(), self.keyframes.last()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the value of the track at time, or None if the track has no
+    /// keyframes. Finds the keyframes around time by binary search.
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let first = self.keyframes.first()?;
+        let duration = self.duration();
+        let time = if duration <= 0.0 {
+            first.time
+        } else {
+            let local = time - first.time;
+            let wrapped = match self.looping {
+                Looping::Clamp => local.clamp(0.0, duration),
+                Looping::Repeat => local.rem_euclid(duration),
+                Looping::PingPong => {
+                    let phase = local.rem_euclid(2.0 * duration);
+                    duration - (phase - duration).abs()
+                }
+            };
+            first.time + wrapped
+        };
+
+        // The segment from keyframe i - 1 to keyframe i contains time
+        let keys = &self.keyframes;
+        let i = keys.partition_point(|k| k.time <= time);
+        if i == 0 {
+            return Some(first.value);
+        }
+        if i == keys.len() {
+            return Some(keys[i - 1].value);
+        }
+        let (a, b) = (&keys[i - 1], &keys[i]);
+        let t = (time - a.time) / (b.time - a.time);
+        Some(match self.interpolation {
+            Interpolation::Step => a.value,
+            Interpolation::Linear => a.value.interpolate(&b.value, t),
+            Interpolation::Cubic => {
+                // Missing neighbors at the ends are mirrored in time and
+                // repeat the end value, which halves the slope at the first
+                // and last keyframe
+                let before = match i.checked_sub(2) {
+                    Some(j) => keys[j],
+                    None => Keyframe {
+                        time: 2.0 * a.time - b.time,
+                        value: a.value,
+                    },
+                };
+                let after = keys.get(i + 1).copied().unwrap_or(Keyframe {
+                    time: 2.0 * b.time - a.time,
+                    value: b.value,
+                });
+                catmull_rom([&before, a, b, &after], time)
+            }
+        })
+    }
+}
+
+/// Evaluates the Catmull-Rom curve through the four keyframes between the
+/// middle two, with the keyframe times as knots. Uses the pyramid of
+/// interpolations by Barry and Goldman, so it only needs Interpolate.
+fn catmull_rom<T: Interpolate>(keys: [&Keyframe<T>; 4], time: f32) -> T {
+    let [k0, k1, k2, k3] = keys;
+    let lerp = |a: &T, b: &T, t0: f32, t1: f32| a.interpolate(b, (time - t0) / (t1 - t0));
+    let a1 = lerp(&k0.value, &k1.value, k0.time, k1.time);
+    let a2 = lerp(&k1.value, &k2.value, k1.time, k2.time);
+    let a3 = lerp(&k2.value, &k3.value, k2.time, k3.time);
+    let b1 = lerp(&a1, &a2, k0.time, k2.time);
+    let b2 = lerp(&a2, &a3, k1.time, k3.time);
+    lerp(&b1, &b2, k1.time, k2.time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframes() -> Vec<Keyframe<f32>> {
+        [(2.0, 4.0), (0.0, 0.0), (1.0, 1.0), (3.0, 2.0)]
+            .iter()
+            .map(|&(time, value)| Keyframe { time, value })
+            .collect()
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut track = Track::from_keyframes(keyframes(), Interpolation::Linear, Looping::Clamp);
+        assert_eq!(track.duration(), 3.0);
+        assert_eq!(track.sample(-1.0), Some(0.0));
+        assert_eq!(track.sample(1.5), Some(2.5));
+        assert_eq!(track.sample(10.0), Some(2.0));
+
+        track.interpolation = Interpolation::Step;
+        assert_eq!(track.sample(1.5), Some(1.0));
+        assert_eq!(track.sample(2.0), Some(4.0));
+
+        track.interpolation = Interpolation::Linear;
+        track.looping = Looping::Repeat;
+        assert_eq!(track.sample(5.0), Some(4.0));
+        track.looping = Looping::PingPong;
+        assert_eq!(track.sample(5.0), Some(1.0));
+
+        // Cubic passes through the keyframes and is continuous between them
+        track.interpolation = Interpolation::Cubic;
+        track.looping = Looping::Clamp;
+        for key in keyframes() {
+            let value = track.sample(key.time).unwrap();
+            assert!((-0.0001..0.0001).contains(&(value - key.value)));
+        }
+        let left = track.sample(0.9999).unwrap();
+        let right = track.sample(1.0001).unwrap();
+        assert!((left - right).abs() < 0.001);
+
+        track.insert(1.0, 3.0);
+        track.insert(0.5, -1.0);
+        assert_eq!(track.keyframes().len(), 5);
+        assert_eq!(track.keyframes()[2].value, 3.0);
+        assert!(Track::<f32>::new(Interpolation::Linear, Looping::Clamp)
+            .sample(1.0)
+            .is_none());
+    }
+
+    #[test]
+    fn test_duplicate_times() {
+        let keyframes = [(0.0, 0.0), (1.0, 1.0), (1.0, 2.0), (2.0, 3.0), (3.0, 0.0)]
+            .iter()
+            .map(|&(time, value)| Keyframe { time, value })
+            .collect();
+        let track = Track::from_keyframes(keyframes, Interpolation::Cubic, Looping::Clamp);
+        assert_eq!(track.keyframes().len(), 4);
+        assert_eq!(track.sample(1.0), Some(2.0));
+        assert!(track.sample(1.5).unwrap().is_finite());
+    }
+
+    #[test]
+    fn test_rotor_track() {
+        let axis = Vec3f32::new(0.0, 1.0, 0.0);
+        let keyframes: Vec<_> = [0.0, 1.0, 2.0, 4.0]
+            .iter()
+            .map(|&time| Keyframe {
+                time,
+                value: Rot3Df32::from_axis_angle(axis, time * 0.5),
+            })
+            .collect();
+        for interpolation in [Interpolation::Linear, Interpolation::Cubic] {
+            let track = Track::from_keyframes(keyframes.clone(), interpolation, Looping::Clamp);
+            // Constant angular velocity, so both modes give the same result
+            // away from the flat ends of the cubic curve
+            let rotor = track.sample(1.5).unwrap();
+            let expected = Rot3Df32::from_axis_angle(axis, 0.75);
+            let v = rotor.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0));
+            let w = expected.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0));
+            assert!((-0.0001..0.0001).contains(&v.distance(w)));
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    angle::wrap::wrap_angle, mat::mat4f32::Mat4f32, rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32,
+};
+use std::f32::consts::FRAC_PI_2;
+
+/// First person camera with +y as up. At yaw and pitch 0 it looks down -z.
+#[derive(Debug, Clone, Copy)]
+pub struct FpsCamera {
+    pub position: Vec3f32,
+    /// Rotation around the world y axis in radians, positive turns left
+    pub yaw: f32,
+    /// Rotation around the local x axis in radians, positive looks up
+    pub pitch: f32,
+    /// The pitch is clamped to -max_pitch..max_pitch
+    pub max_pitch: f32,
+}
+
+impl FpsCamera {
+    pub fn new(position: Vec3f32, yaw: f32, pitch: f32) -> Self {
+        let max_pitch = FRAC_PI_2 - 0.001;
+        Self {
+            position,
+            yaw,
+            pitch: pitch.clamp(-max_pitch, max_pitch),
+            max_pitch,
+        }
+    }
+
+    /// Turns the camera, the pitch is clamped so the camera can't flip over
+    pub fn look(&mut self, delta_yaw: f32, delta_pitch: f32) {
+        self.yaw = wrap_angle(self.yaw + delta_yaw);
+        self.pitch = (self.pitch + delta_pitch).clamp(-self.max_pitch, self.max_pitch);
+    }
+
+    /// Returns the rotor that rotates camera space into world space
+    pub fn orientation(&self) -> Rot3Df32 {
+        let pitch = Rot3Df32::from_axis_angle(Vec3f32::new(1.0, 0.0, 0.0), self.pitch);
+        let yaw = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), self.yaw);
+        yaw.appended(pitch)
+    }
+
+    /// Returns the direction the camera looks at
+    pub fn forward(&self) -> Vec3f32 {
+        self.orientation().rotated_vec(Vec3f32::new(0.0, 0.0, -1.0))
+    }
+
+    pub fn right(&self) -> Vec3f32 {
+        self.orientation().rotated_vec(Vec3f32::new(1.0, 0.0, 0.0))
+    }
+
+    /// Moves the camera along its own axes (x right, y up, -z forward),
+    /// so moving forward while looking up also moves up
+    pub fn move_local(&mut self, delta: Vec3f32) {
+        self.position = self.position + self.orientation().rotated_vec(delta);
+    }
+
+    /// Like move_local, but ignores the pitch so the camera stays at the
+    /// same height when moving forward (walking)
+    pub fn move_planar(&mut self, delta: Vec3f32) {
+        let yaw = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), self.yaw);
+        self.position = self.position + yaw.rotated_vec(delta);
+    }
+
+    /// Returns the matrix that transforms world space into camera space
+    pub fn view_matrix(&self) -> Mat4f32 {
+        Mat4f32::view_from_pose(self.position, &self.orientation())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_look_and_move() {
+        let mut camera = FpsCamera::new(Vec3f32::new(0.0, 1.0, 0.0), 0.0, 0.0);
+        camera.look(FRAC_PI_2, 0.0);
+        let forward = camera.forward();
+        assert!((-1.0001..-0.9999).contains(&forward.x));
+        assert!((-0.0001..0.0001).contains(&forward.z));
+
+        camera.look(0.0, 10.0);
+        assert!((camera.max_pitch - 0.0001..camera.max_pitch + 0.0001).contains(&camera.pitch));
+        camera.look(0.0, -camera.max_pitch);
+
+        camera.move_local(Vec3f32::new(0.0, 0.0, -2.0));
+        assert!((-2.0001..-1.9999).contains(&camera.position.x));
+        assert!((0.9999..1.0001).contains(&camera.position.y));
+
+        camera.look(0.0, 0.5);
+        camera.move_planar(Vec3f32::new(0.0, 0.0, -1.0));
+        assert!((-3.0001..-2.9999).contains(&camera.position.x));
+        assert!((0.9999..1.0001).contains(&camera.position.y));
+    }
+
+    #[test]
+    fn test_view_matrix() {
+        let camera = FpsCamera::new(Vec3f32::new(1.0, 2.0, 3.0), 0.3, -0.4);
+        let view = camera.view_matrix();
+
+        let origin = view.transform_point(camera.position);
+        assert!((-0.0001..0.0001).contains(&origin.magnitude()));
+
+        // Yaw is applied after the pitch, so the forward vector keeps the pitch angle
+        let forward = camera.forward();
+        assert!((-0.3895..-0.3893).contains(&forward.y));
+        assert!((-0.2723..-0.2721).contains(&forward.x));
+
+        let ahead = view.transform_point(camera.position + camera.forward() * 5.0);
+        assert!((-0.0001..0.0001).contains(&ahead.x));
+        assert!((-0.0001..0.0001).contains(&ahead.y));
+        assert!((-5.0001..-4.9999).contains(&ahead.z));
+    }
+}
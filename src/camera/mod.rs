@@ -0,0 +1,3 @@
+pub mod fps_camera;
+pub mod orbit_camera;
+pub mod spring_arm;
@@ -0,0 +1,143 @@
+use crate::{
+    float,
+    mat::mat4f32::Mat4f32,
+    rotor::rot3df32::Rot3Df32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// Camera orbiting around a target point, for editors and model viewers.
+/// The camera sits at distance along the local +z axis and looks at the target.
+#[derive(Debug, Clone, Copy)]
+pub struct OrbitCamera {
+    pub target: Vec3f32,
+    pub distance: f32,
+    /// Rotates camera space into world space
+    pub orientation: Rot3Df32,
+    /// zoom never brings the camera closer to the target than this
+    pub min_distance: f32,
+}
+
+impl OrbitCamera {
+    pub fn new(target: Vec3f32, distance: f32) -> Self {
+        Self {
+            target,
+            distance,
+            orientation: Rot3Df32::identity(),
+            min_distance: 0.01,
+        }
+    }
+
+    /// Returns the position of the camera in world space
+    pub fn position(&self) -> Vec3f32 {
+        self.target
+            + self
+                .orientation
+                .rotated_vec(Vec3f32::new(0.0, 0.0, self.distance))
+    }
+
+    /// Orbits around the world y axis by delta_yaw and around the local x
+    /// axis by delta_pitch (both in radians). Positive yaw moves the camera
+    /// to the right, positive pitch moves it up.
+    pub fn rotate(&mut self, delta_yaw: f32, delta_pitch: f32) {
+        let pitch = Rot3Df32::from_axis_angle(Vec3f32::new(1.0, 0.0, 0.0), -delta_pitch);
+        let yaw = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), delta_yaw);
+        self.orientation = yaw.appended(self.orientation.appended(pitch));
+        self.orientation.normalize();
+    }
+
+    /// Arcball rotation for a mouse drag from `from` to `to`, both in
+    /// normalized screen coordinates (-1..1, y up). The scene follows the
+    /// mouse as if it was grabbed on a sphere around the target.
+    pub fn arcball(&mut self, from: Vec2f32, to: Vec2f32) {
+        let a = arcball_point(from);
+        let b = arcball_point(to);
+        if (a - b).magnitude_squared() < 1e-12 {
+            return;
+        }
+        // Rotating the scene by a->b is rotating the camera by b->a
+        let rotation = Rot3Df32::new_exact(b, a);
+        self.orientation = self.orientation.appended(rotation);
+        self.orientation.normalize();
+    }
+
+    /// Multiplies the distance by factor, values below 1 zoom in
+    pub fn zoom(&mut self, factor: f32) {
+        self.distance = (self.distance * factor).max(self.min_distance);
+    }
+
+    /// Moves the target in the view plane. delta is given in units of the
+    /// distance, so the same screen drag moves the scene by the same amount
+    /// independent of the zoom.
+    pub fn pan(&mut self, delta: Vec2f32) {
+        let offset = Vec3f32::new(delta.x, delta.y, 0.0) * self.distance;
+        self.target = self.target + self.orientation.rotated_vec(offset);
+    }
+
+    /// Returns the matrix that transforms world space into camera space
+    pub fn view_matrix(&self) -> Mat4f32 {
+        Mat4f32::view_from_pose(self.position(), &self.orientation)
+    }
+}
+
+/// Maps a point in normalized screen coordinates onto the arcball. Points
+/// outside of the ball are mapped onto a hyperbolic sheet (Holroyd) so the
+/// rotation stays continuous.
+fn arcball_point(p: Vec2f32) -> Vec3f32 {
+    let r2 = 1.0;
+    let d2 = p.magnitude_squared();
+    let z = if d2 <= r2 / 2.0 {
+        float::sqrt(r2 - d2)
+    } else {
+        r2 / 2.0 / float::sqrt(d2)
+    };
+    let mut result = Vec3f32::new(p.x, p.y, z);
+    result.normalize();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_rotate() {
+        let mut camera = OrbitCamera::new(Vec3f32::new(1.0, 0.0, 0.0), 2.0);
+        let p = camera.position();
+        assert!((1.9999..2.0001).contains(&p.z));
+
+        camera.rotate(FRAC_PI_2, 0.0);
+        let p = camera.position();
+        assert!((2.9999..3.0001).contains(&p.x));
+        assert!((-0.0001..0.0001).contains(&p.z));
+
+        camera.rotate(0.0, FRAC_PI_2 / 2.0);
+        let p = camera.position();
+        assert!((1.4141..1.4143).contains(&p.y));
+
+        // The target stays in the center of the view
+        let center = camera.view_matrix().transform_point(camera.target);
+        assert!((-0.0001..0.0001).contains(&center.x));
+        assert!((-0.0001..0.0001).contains(&center.y));
+        assert!((-2.0001..-1.9999).contains(&center.z));
+    }
+
+    #[test]
+    fn test_arcball_zoom_pan() {
+        let mut camera = OrbitCamera::new(Vec3f32::new(0.0, 0.0, 0.0), 4.0);
+        // Dragging right rotates the scene to the right, so the camera
+        // moves to the left
+        camera.arcball(Vec2f32::new(0.0, 0.0), Vec2f32::new(0.5, 0.0));
+        assert!(camera.position().x < 0.0);
+        assert!((3.9999..4.0001).contains(&camera.position().magnitude()));
+
+        camera.zoom(0.5);
+        assert!((1.9999..2.0001).contains(&camera.distance));
+        camera.zoom(0.0);
+        assert!((0.0099..0.0101).contains(&camera.distance));
+
+        let mut camera = OrbitCamera::new(Vec3f32::new(0.0, 0.0, 0.0), 4.0);
+        camera.pan(Vec2f32::new(0.5, 0.0));
+        assert!((1.9999..2.0001).contains(&camera.target.x));
+    }
+}
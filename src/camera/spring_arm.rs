@@ -0,0 +1,117 @@
+use crate::{
+    anim::smooth_damp::smooth_damp,
+    geometry::{aabb3f32::Aabb3f32, ray3f32::Ray3f32},
+    rotor::rot3df32::Rot3Df32,
+    transform::pose3f32::Pose3f32,
+    vec::vec3::Vec3f32,
+};
+
+/// Camera boom for third person cameras. The camera sits up to length
+/// behind the pivot along the local +z axis (looking at the pivot) and is
+/// pulled in when obstacles are in the way, so it never ends up behind a
+/// wall. It snaps in immediately and extends again smoothly.
+#[derive(Debug, Clone, Copy)]
+pub struct SpringArm {
+    /// The length of the arm without obstacles
+    pub length: f32,
+    /// Radius of the sphere swept along the arm, the distance the camera
+    /// keeps from obstacles
+    pub probe_radius: f32,
+    /// Roughly the time it takes to extend again, see smooth_damp
+    pub smooth_time: f32,
+    current_length: f32,
+    velocity: f32,
+}
+
+impl SpringArm {
+    pub fn new(length: f32, probe_radius: f32, smooth_time: f32) -> Self {
+        Self {
+            length,
+            probe_radius,
+            smooth_time,
+            current_length: length,
+            velocity: 0.0,
+        }
+    }
+
+    /// Returns the length of the arm after the last update
+    pub fn current_length(&self) -> f32 {
+        self.current_length
+    }
+
+    /// Sweeps the probe sphere from pivot along the normalized direction dir
+    /// and returns how far it gets, at most length. The boxes are grown by
+    /// the probe radius, which is slightly conservative near their edges.
+    /// Boxes that already contain the pivot are ignored.
+    pub fn cast(&self, pivot: Vec3f32, dir: Vec3f32, obstacles: &[Aabb3f32]) -> f32 {
+        let ray = Ray3f32::new(pivot, dir);
+        let margin = Vec3f32::new(self.probe_radius, self.probe_radius, self.probe_radius);
+        obstacles
+            .iter()
+            .filter_map(|aabb| {
+                ray.intersect_aabb(&Aabb3f32::new(aabb.min - margin, aabb.max + margin))
+            })
+            // Exit hits come from boxes around the pivot
+            .filter(|hit| hit.normal.dot(dir) < 0.0)
+            .map(|hit| hit.t)
+            .fold(self.length, f32::min)
+    }
+
+    /// Moves the arm for this frame and returns the pose of the camera. The
+    /// arm points from pivot along the local +z axis of rotation, which also
+    /// becomes the camera rotation.
+    pub fn update(
+        &mut self,
+        pivot: Vec3f32,
+        rotation: Rot3Df32,
+        obstacles: &[Aabb3f32],
+        dt: f32,
+    ) -> Pose3f32 {
+        let dir = rotation.rotated_vec(Vec3f32::new(0.0, 0.0, 1.0));
+        let free = self.cast(pivot, dir, obstacles);
+        if free < self.current_length {
+            self.current_length = free;
+            self.velocity = 0.0;
+        } else {
+            self.current_length = smooth_damp(
+                self.current_length,
+                free,
+                &mut self.velocity,
+                self.smooth_time,
+                dt,
+            )
+            .min(free);
+        }
+        Pose3f32::new(pivot + dir * self.current_length, rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spring_arm() {
+        let mut arm = SpringArm::new(5.0, 0.2, 0.3);
+        let pivot = Vec3f32::new(0.0, 1.0, 0.0);
+        let rotation = Rot3Df32::identity();
+
+        let pose = arm.update(pivot, rotation, &[], 1.0 / 60.0);
+        assert!((-0.0001..0.0001).contains(&pose.position.distance(Vec3f32::new(0.0, 1.0, 5.0))));
+
+        // A wall behind the pivot and a box around the pivot that is ignored
+        let wall = Aabb3f32::new(Vec3f32::new(-5.0, -5.0, 3.0), Vec3f32::new(5.0, 5.0, 4.0));
+        let around = Aabb3f32::new(Vec3f32::new(-0.5, 0.0, -0.5), Vec3f32::new(0.5, 2.0, 0.5));
+        let pose = arm.update(pivot, rotation, &[wall, around], 1.0 / 60.0);
+        assert!((2.7999..2.8001).contains(&pose.position.z));
+        assert_eq!(pose.rotation, rotation);
+
+        // Without the wall, the arm extends smoothly
+        let pose = arm.update(pivot, rotation, &[around], 1.0 / 60.0);
+        assert!(pose.position.z > 2.8 && pose.position.z < 3.5);
+        for _ in 0..300 {
+            arm.update(pivot, rotation, &[around], 1.0 / 60.0);
+        }
+        assert!((4.99..5.0001).contains(&arm.current_length()));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{color::linear_rgba::LinearRgba, float};
+
+/// Lumens per watt of light at 555 nm, where the eye is most sensitive
+pub const MAX_LUMINOUS_EFFICACY: f32 = 683.0;
+
+/// Returns the color of a black body (an incandescent light, the sun) at
+/// temperature (in kelvin), scaled to a luminance of 1 so it can be
+/// multiplied with a photometric intensity. Uses the cubic spline fit of
+/// the Planckian locus by Kim et al., temperature is clamped to the range
+/// 1667..25000 where it is valid. 6500 K is close to white.
+pub fn color_from_kelvin(temperature: f32) -> LinearRgba {
+    let t = temperature.clamp(1667.0, 25000.0);
+    let (t2, t3) = (t * t, t * t * t);
+    let x = if t <= 4000.0 {
+        -0.266_123_9e9 / t3 - 0.234_358_9e6 / t2 + 0.877_695_6e3 / t + 0.179_910
+    } else {
+        -3.025_846_9e9 / t3 + 2.107_038e6 / t2 + 0.222_634_7e3 / t + 0.240_390
+    };
+    let (x2, x3) = (x * x, x * x * x);
+    let y = if t <= 2222.0 {
+        -1.106_381_4 * x3 - 1.348_110_2 * x2 + 2.185_558_3 * x - 0.202_196_83
+    } else if t <= 4000.0 {
+        -0.954_947_6 * x3 - 1.374_185_9 * x2 + 2.091_37 * x - 0.167_488_67
+    } else {
+        3.081_758 * x3 - 5.873_387 * x2 + 3.751_13 * x - 0.370_014_83
+    };
+
+    // CIE xyY with Y = 1 to XYZ to linear sRGB. Very warm colors are
+    // outside of the sRGB gamut, their negative blue is clipped.
+    let (cx, cy, cz) = (x / y, 1.0, (1.0 - x - y) / y);
+    let color = LinearRgba::rgb(
+        (3.240_454_2 * cx - 1.537_138_5 * cy - 0.498_531_4 * cz).max(0.0),
+        (-0.969_266 * cx + 1.876_010_8 * cy + 0.041_556 * cz).max(0.0),
+        (0.055_643_4 * cx - 0.204_025_9 * cy + 1.057_225_2 * cz).max(0.0),
+    );
+    color * (1.0 / color.luminance())
+}
+
+/// Returns the luminous efficacy (in lumens per watt) of black body
+/// radiation at temperature (in kelvin): the share of the radiated power
+/// that the eye can see, weighted by its sensitivity. The sun (about
+/// 5800 K) gives about 93 lm/W and a 2700 K incandescent light about 14 lm/W.
+pub fn blackbody_luminous_efficacy(temperature: f32) -> f32 {
+    // Planck's law times a gaussian fit of the photopic luminosity function
+    // V, integrated over the visible range in 5 nm steps, relative to the
+    // total radiance (Stefan-Boltzmann law)
+    const C1: f32 = 1.191_043e-16; // 2hc^2 in W m^2 / sr
+    const C2: f32 = 1.438_776_9e-2; // hc/k in m K
+    const SIGMA: f32 = 5.670_374_4e-8;
+    let step = 5e-9;
+    let mut visible = 0.0;
+    for i in 0..=80 {
+        let wavelength = 380e-9 + i as f32 * step;
+        let microns = wavelength * 1e6;
+        let v = 1.019 * float::exp(-285.4 * (microns - 0.559) * (microns - 0.559));
+        let radiance = C1
+            / (float::powf(wavelength, 5.0) * (float::exp(C2 / (wavelength * temperature)) - 1.0));
+        visible += radiance * v * step;
+    }
+    let total =
+        SIGMA * temperature * temperature * temperature * temperature / std::f32::consts::PI;
+    MAX_LUMINOUS_EFFICACY * visible / total
+}
+
+/// Converts a photometric quantity of black body light (lumens, candela
+/// or nits) to the radiometric RGB quantity (watts, W/sr or W/(sr m^2))
+/// that a renderer working in radiometric units expects, for example a
+/// 5600 K key light at 1000 lumens
+pub fn blackbody_radiometric(temperature: f32, photometric: f32) -> LinearRgba {
+    color_from_kelvin(temperature) * (photometric / blackbody_luminous_efficacy(temperature))
+}
+
+/// Returns the luminous intensity (in candela) of a point light that emits
+/// lumens equally in all directions
+pub fn point_light_intensity(lumens: f32) -> f32 {
+    lumens / (4.0 * std::f32::consts::PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_from_kelvin() {
+        let white = color_from_kelvin(6500.0);
+        for c in [white.r, white.g, white.b] {
+            assert!((0.95..1.05).contains(&c));
+        }
+        let warm = color_from_kelvin(2700.0);
+        assert!(warm.r > warm.g && warm.g > warm.b);
+        let cold = color_from_kelvin(12000.0);
+        assert!(cold.b > cold.g && cold.g > cold.r);
+        for color in [warm, cold, color_from_kelvin(1000.0)] {
+            assert!((0.9999..1.0001).contains(&color.luminance()));
+            assert_eq!(color.a, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_luminous_efficacy() {
+        assert!((88.0..98.0).contains(&blackbody_luminous_efficacy(5800.0)));
+        assert!((11.0..17.0).contains(&blackbody_luminous_efficacy(2700.0)));
+        let key_light = blackbody_radiometric(5600.0, 1000.0);
+        let efficacy = blackbody_luminous_efficacy(5600.0);
+        assert!((0.999..1.001).contains(&(key_light.luminance() * efficacy / 1000.0)));
+        assert!((79.5..79.6).contains(&point_light_intensity(1000.0)));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::ops::forward_ref_binop;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Mul};
+
+/// A color with linear (not gamma encoded) sRGB / Rec.709 components,
+/// the space lighting is computed in. Components may exceed 1 for HDR.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LinearRgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl LinearRgba {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Returns an opaque color
+    pub fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::new(r, g, b, 1.0)
+    }
+
+    /// Returns the relative luminance (Rec.709 weights), ignoring alpha
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+}
+
+impl Add for LinearRgba {
+    type Output = LinearRgba;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        LinearRgba::new(
+            self.r + rhs.r,
+            self.g + rhs.g,
+            self.b + rhs.b,
+            self.a + rhs.a,
+        )
+    }
+}
+
+/// Scales the color components, alpha stays the same
+impl Mul<f32> for LinearRgba {
+    type Output = LinearRgba;
+
+    fn mul(mut self, rhs: f32) -> Self::Output {
+        self.r *= rhs;
+        self.g *= rhs;
+        self.b *= rhs;
+        self
+    }
+}
+
+forward_ref_binop!(impl Add, add for LinearRgba, LinearRgba);
+forward_ref_binop!(impl Mul, mul for LinearRgba, f32);
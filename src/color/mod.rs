@@ -0,0 +1,2 @@
+pub mod kelvin;
+pub mod linear_rgba;
@@ -0,0 +1,157 @@
+use crate::ops::{forward_ref_binop, forward_ref_unop};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Signed 32.32 fixed point number. All operations are integer operations,
+/// so results are bit-identical on every platform, which lockstep
+/// simulations rely on. Only convert to floats at the rendering boundary.
+///
+/// The range is -2^31 to 2^31 - 2^-32. All arithmetic saturates at
+/// Fx32::MIN and Fx32::MAX instead of wrapping or panicking, so debug and
+/// release builds produce the same results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fx32 {
+    /// The value multiplied by 2^32
+    pub raw: i64,
+}
+
+impl Fx32 {
+    pub const FRACTION_BITS: u32 = 32;
+    pub const ZERO: Fx32 = Fx32 { raw: 0 };
+    pub const MIN: Fx32 = Fx32 { raw: i64::MIN };
+    pub const MAX: Fx32 = Fx32 { raw: i64::MAX };
+    pub const ONE: Fx32 = Fx32 {
+        raw: 1 << Self::FRACTION_BITS,
+    };
+
+    pub fn from_raw(raw: i64) -> Self {
+        Self { raw }
+    }
+
+    pub fn from_int(value: i32) -> Self {
+        Self {
+            raw: (value as i64) << Self::FRACTION_BITS,
+        }
+    }
+
+    /// Converts a float, rounding to the nearest representable value. Not
+    /// guaranteed to be deterministic if value was computed with floats.
+    pub fn from_f32(value: f32) -> Self {
+        Self::from_f64(value as f64)
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        Self {
+            raw: (value * Self::ONE.raw as f64).round() as i64,
+        }
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.raw as f64 / Self::ONE.raw as f64
+    }
+
+    pub fn abs(self) -> Self {
+        Self {
+            raw: self.raw.saturating_abs(),
+        }
+    }
+
+    /// Converts a wide intermediate result, saturating at MIN and MAX
+    fn from_wide(raw: i128) -> Self {
+        Self {
+            raw: raw.clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+        }
+    }
+
+    /// Returns the square root, rounded down. Negative values return 0.
+    pub fn sqrt(self) -> Self {
+        if self.raw <= 0 {
+            return Self::ZERO;
+        }
+        let scaled = (self.raw as u128) << Self::FRACTION_BITS;
+        Self {
+            raw: scaled.isqrt() as i64,
+        }
+    }
+}
+
+impl Add for Fx32 {
+    type Output = Fx32;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fx32::from_raw(self.raw.saturating_add(rhs.raw))
+    }
+}
+
+impl Sub for Fx32 {
+    type Output = Fx32;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Fx32::from_raw(self.raw.saturating_sub(rhs.raw))
+    }
+}
+
+impl Neg for Fx32 {
+    type Output = Fx32;
+
+    fn neg(self) -> Self::Output {
+        Fx32::from_raw(self.raw.saturating_neg())
+    }
+}
+
+impl Mul for Fx32 {
+    type Output = Fx32;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Fx32::from_wide((self.raw as i128 * rhs.raw as i128) >> Fx32::FRACTION_BITS)
+    }
+}
+
+impl Div for Fx32 {
+    type Output = Fx32;
+
+    /// Panics if rhs is 0
+    fn div(self, rhs: Self) -> Self::Output {
+        Fx32::from_wide(((self.raw as i128) << Fx32::FRACTION_BITS) / rhs.raw as i128)
+    }
+}
+
+forward_ref_binop!(impl Add, add for Fx32, Fx32);
+forward_ref_binop!(impl Sub, sub for Fx32, Fx32);
+forward_ref_binop!(impl Mul, mul for Fx32, Fx32);
+forward_ref_binop!(impl Div, div for Fx32, Fx32);
+forward_ref_unop!(impl Neg, neg for Fx32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Fx32::from_f32(2.5);
+        let b = Fx32::from_int(-4);
+        assert_eq!((a * b).to_f32(), -10.0);
+        assert_eq!((b / a).to_f32(), -1.6);
+        assert_eq!((a + b).to_f32(), -1.5);
+        assert_eq!((a - b).to_f32(), 6.5);
+        assert_eq!(Fx32::from_int(9).sqrt(), Fx32::from_int(3));
+        let sqrt_2 = std::f64::consts::SQRT_2;
+        assert!((sqrt_2 - 1e-6..sqrt_2 + 1e-6).contains(&Fx32::from_int(2).sqrt().to_f64()));
+        assert_eq!(Fx32::ONE.raw, 1 << 32);
+    }
+
+    #[test]
+    fn test_saturation() {
+        let big = Fx32::from_int(i32::MAX);
+        assert_eq!(big * big, Fx32::MAX);
+        assert_eq!(big * -big, Fx32::MIN);
+        assert_eq!(Fx32::MAX + Fx32::ONE, Fx32::MAX);
+        assert_eq!(Fx32::MIN - Fx32::ONE, Fx32::MIN);
+        assert_eq!(-Fx32::MIN, Fx32::MAX);
+        assert_eq!(Fx32::MIN.abs(), Fx32::MAX);
+        assert_eq!(big / Fx32::from_raw(1), Fx32::MAX);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod fx32;
+pub mod vec3fx;
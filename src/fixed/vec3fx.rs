@@ -0,0 +1,149 @@
+use crate::{
+    fixed::fx32::Fx32,
+    ops::{forward_ref_binop, forward_ref_unop},
+    vec::vec3::Vec3f32,
+};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Deterministic 3D vector with 32.32 fixed point components
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vec3fx {
+    pub x: Fx32,
+    pub y: Fx32,
+    pub z: Fx32,
+}
+
+impl Vec3fx {
+    pub fn new(x: Fx32, y: Fx32, z: Fx32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Converts a float vector, rounding each component to the nearest
+    /// representable value
+    pub fn from_vec3(v: Vec3f32) -> Self {
+        Self::new(
+            Fx32::from_f32(v.x),
+            Fx32::from_f32(v.y),
+            Fx32::from_f32(v.z),
+        )
+    }
+
+    pub fn to_vec3(self) -> Vec3f32 {
+        Vec3f32::new(self.x.to_f32(), self.y.to_f32(), self.z.to_f32())
+    }
+
+    pub fn dot(&self, v: Self) -> Fx32 {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+
+    pub fn cross(&self, v: Self) -> Self {
+        Self::new(
+            self.y * v.z - self.z * v.y,
+            self.z * v.x - self.x * v.z,
+            self.x * v.y - self.y * v.x,
+        )
+    }
+
+    /// Saturates at Fx32::MAX for vectors longer than about 46340
+    /// (sqrt(2^31)), see magnitude
+    pub fn magnitude_squared(&self) -> Fx32 {
+        self.dot(*self)
+    }
+
+    /// Only exact for vectors shorter than about 46340, longer vectors
+    /// return about 46340 because magnitude_squared saturates
+    pub fn magnitude(&self) -> Fx32 {
+        self.magnitude_squared().sqrt()
+    }
+
+    /// Returns the vector with length 1 in the same direction.
+    /// Make sure self is not 0.
+    pub fn normalized(&self) -> Self {
+        *self / self.magnitude()
+    }
+}
+
+impl From<Vec3f32> for Vec3fx {
+    fn from(v: Vec3f32) -> Self {
+        Self::from_vec3(v)
+    }
+}
+
+impl From<Vec3fx> for Vec3f32 {
+    fn from(v: Vec3fx) -> Self {
+        v.to_vec3()
+    }
+}
+
+impl Add for Vec3fx {
+    type Output = Vec3fx;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec3fx::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3fx {
+    type Output = Vec3fx;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3fx::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vec3fx {
+    type Output = Vec3fx;
+
+    fn neg(self) -> Self::Output {
+        Vec3fx::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<Fx32> for Vec3fx {
+    type Output = Vec3fx;
+
+    fn mul(self, rhs: Fx32) -> Self::Output {
+        Vec3fx::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Div<Fx32> for Vec3fx {
+    type Output = Vec3fx;
+
+    fn div(self, rhs: Fx32) -> Self::Output {
+        Vec3fx::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+forward_ref_binop!(impl Add, add for Vec3fx, Vec3fx);
+forward_ref_binop!(impl Sub, sub for Vec3fx, Vec3fx);
+forward_ref_binop!(impl Mul, mul for Vec3fx, Fx32);
+forward_ref_binop!(impl Div, div for Vec3fx, Fx32);
+forward_ref_unop!(impl Neg, neg for Vec3fx);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec3fx() {
+        let a = Vec3fx::from_vec3(Vec3f32::new(1.0, 2.0, 2.0));
+        let b = Vec3fx::from(Vec3f32::new(0.0, 1.0, 0.0));
+        assert_eq!(a.magnitude(), Fx32::from_int(3));
+        assert_eq!(a.dot(b), Fx32::from_int(2));
+
+        let c = a.cross(b).to_vec3();
+        assert_eq!((c.x, c.y, c.z), (-2.0, 0.0, 1.0));
+
+        let n = a.normalized().to_vec3();
+        assert!((0.3333..0.3334).contains(&n.x));
+
+        let v: Vec3f32 = (a * Fx32::from_f32(0.5) - b).into();
+        assert_eq!((v.x, v.y, v.z), (0.5, 0.0, 1.0));
+
+        // Saturates instead of wrapping around to a negative value
+        let far = Vec3fx::new(Fx32::from_int(50000), Fx32::ZERO, Fx32::ZERO);
+        assert_eq!(far.magnitude_squared(), Fx32::MAX);
+        assert!(far.magnitude() > Fx32::from_int(46340));
+    }
+}
@@ -0,0 +1,82 @@
+// Float functions whose results may differ between platforms and standard
+// library versions. With the deterministic feature they are computed by
+// libm in plain software, which gives bit-identical results on every
+// target (x86, ARM, wasm). The remaining operations (+, -, *, /) are
+// IEEE 754 exact on all of them, and rustc never contracts a * b + c into
+// a fused multiply add on its own, so results only differ if the caller
+// uses mul_add or float types with a different precision.
+
+macro_rules! unary {
+    ($name:ident, $libm:ident) => {
+        #[inline]
+        pub(crate) fn $name(x: f32) -> f32 {
+            #[cfg(feature = "deterministic")]
+            {
+                libm::$libm(x)
+            }
+            #[cfg(not(feature = "deterministic"))]
+            {
+                x.$name()
+            }
+        }
+    };
+}
+
+macro_rules! binary {
+    ($name:ident, $libm:ident) => {
+        #[inline]
+        pub(crate) fn $name(x: f32, y: f32) -> f32 {
+            #[cfg(feature = "deterministic")]
+            {
+                libm::$libm(x, y)
+            }
+            #[cfg(not(feature = "deterministic"))]
+            {
+                x.$name(y)
+            }
+        }
+    };
+}
+
+unary!(sqrt, sqrtf);
+unary!(tan, tanf);
+unary!(exp, expf);
+unary!(exp2, exp2f);
+unary!(log2, log2f);
+unary!(acos, acosf);
+binary!(atan2, atan2f);
+binary!(powf, powf);
+
+/// Returns (sin(x), cos(x))
+#[inline]
+pub(crate) fn sin_cos(x: f32) -> (f32, f32) {
+    #[cfg(feature = "deterministic")]
+    {
+        libm::sincosf(x)
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.sin_cos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    #[test]
+    fn test_float_functions() {
+        assert_eq!(sqrt(16.0), 4.0);
+        let (s, c) = sin_cos(0.5);
+        assert!((-0.0001..0.0001).contains(&(s - 0.5f32.sin())));
+        assert!((-0.0001..0.0001).contains(&(c - 0.5f32.cos())));
+        assert!((0.9999..1.0001).contains(&tan(FRAC_PI_4)));
+        assert!((-0.0001..0.0001).contains(&acos(1.0)));
+        assert!((-0.0001..0.0001).contains(&(exp(1.0) - std::f32::consts::E)));
+        assert!((-0.0001..0.0001).contains(&(atan2(1.0, 1.0) - FRAC_PI_4)));
+        assert!((7.9999..8.0001).contains(&powf(2.0, 3.0)));
+        assert!((7.9999..8.0001).contains(&exp2(3.0)));
+        assert!((2.9999..3.0001).contains(&log2(8.0)));
+    }
+}
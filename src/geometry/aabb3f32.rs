@@ -0,0 +1,90 @@
+use crate::vec::vec3::Vec3f32;
+
+/// Axis aligned bounding box
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb3f32 {
+    pub min: Vec3f32,
+    pub max: Vec3f32,
+}
+
+impl Aabb3f32 {
+    pub fn new(min: Vec3f32, max: Vec3f32) -> Self {
+        Self { min, max }
+    }
+
+    /// Returns the tightest box around the points.
+    /// Returns a box with zero size at the origin if points is empty.
+    pub fn from_points(points: &[Vec3f32]) -> Self {
+        let Some(&first) = points.first() else {
+            let origin = Vec3f32::new(0.0, 0.0, 0.0);
+            return Self::new(origin, origin);
+        };
+        let mut result = Self::new(first, first);
+        for &p in &points[1..] {
+            result.min = result.min.min(p);
+            result.max = result.max.max(p);
+        }
+        result
+    }
+
+    pub fn center(&self) -> Vec3f32 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn half_extents(&self) -> Vec3f32 {
+        (self.max - self.min) / 2.0
+    }
+
+    /// Returns true if p is inside or on the surface of the box
+    pub fn contains_point(&self, p: Vec3f32) -> bool {
+        p.x >= self.min.x
+            && p.x <= self.max.x
+            && p.y >= self.min.y
+            && p.y <= self.max.y
+            && p.z >= self.min.z
+            && p.z <= self.max.z
+    }
+
+    /// Returns the 8 corners of the box, bit 0 of the index selects max.x,
+    /// bit 1 max.y and bit 2 max.z
+    pub fn corners(&self) -> [Vec3f32; 8] {
+        std::array::from_fn(|i| {
+            Vec3f32::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            )
+        })
+    }
+
+    /// Returns the smallest box that contains both self and other
+    pub fn merged(&self, other: &Aabb3f32) -> Self {
+        let mut result = *self;
+        result.merge(other);
+        result
+    }
+
+    /// Grows this box so it also contains other
+    pub fn merge(&mut self, other: &Aabb3f32) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_points() {
+        let aabb = Aabb3f32::from_points(&[
+            Vec3f32::new(1.0, -2.0, 0.5),
+            Vec3f32::new(-1.0, 3.0, 0.0),
+            Vec3f32::new(0.0, 0.0, 2.0),
+        ]);
+        assert_eq!((aabb.min.x, aabb.min.y, aabb.min.z), (-1.0, -2.0, 0.0));
+        assert_eq!((aabb.max.x, aabb.max.y, aabb.max.z), (1.0, 3.0, 2.0));
+        assert!(aabb.contains_point(Vec3f32::new(0.0, 0.0, 1.0)));
+        assert!(!aabb.contains_point(Vec3f32::new(0.0, 0.0, 2.5)));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    float,
+    geometry::{
+        ray3f32::{Ray3f32, RayHit},
+        segment3f32::Segment3f32,
+        sphere::Sphere,
+    },
+    vec::vec3::Vec3f32,
+};
+
+/// All points within radius of the segment (a cylinder with hemispherical caps)
+#[derive(Debug, Clone, Copy)]
+pub struct Capsule3f32 {
+    pub segment: Segment3f32,
+    pub radius: f32,
+}
+
+impl Capsule3f32 {
+    pub fn new(a: Vec3f32, b: Vec3f32, radius: f32) -> Self {
+        Self {
+            segment: Segment3f32::new(a, b),
+            radius,
+        }
+    }
+
+    /// Returns the total length from the tip of one cap to the other
+    pub fn length(&self) -> f32 {
+        self.segment.length() + 2.0 * self.radius
+    }
+
+    /// Returns true if p is inside or on the surface of the capsule
+    pub fn contains_point(&self, p: Vec3f32) -> bool {
+        (p - self.segment.closest_point(p)).magnitude_squared() <= self.radius * self.radius
+    }
+
+    /// Returns the point inside or on the surface of the capsule closest to p
+    pub fn closest_point(&self, p: Vec3f32) -> Vec3f32 {
+        Sphere::new(self.segment.closest_point(p), self.radius).closest_point(p)
+    }
+
+    pub fn intersects_capsule(&self, other: &Capsule3f32) -> bool {
+        let (a, b) = self.segment.closest_points(&other.segment);
+        let radius = self.radius + other.radius;
+        (a - b).magnitude_squared() <= radius * radius
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        let p = self.segment.closest_point(sphere.center);
+        let radius = self.radius + sphere.radius;
+        (p - sphere.center).magnitude_squared() <= radius * radius
+    }
+
+    /// Returns the first hit with the surface of the capsule in front of the
+    /// ray origin. If the origin is inside the capsule, this is where the ray exits.
+    pub fn intersect_ray(&self, ray: &Ray3f32) -> Option<RayHit> {
+        let a = self.segment.a;
+        let axis = self.segment.b - self.segment.a;
+        let axis_len_sqrd = axis.magnitude_squared();
+
+        // Candidate distances, hits with the caps and the infinite cylinder
+        let mut candidates = [f32::NAN; 6];
+        sphere_roots(ray, a, self.radius, &mut candidates[0..2]);
+        sphere_roots(ray, self.segment.b, self.radius, &mut candidates[2..4]);
+        if axis_len_sqrd > 0.0 {
+            let perpendicular = |v: Vec3f32| v - axis * (v.dot(axis) / axis_len_sqrd);
+            let m = perpendicular(ray.origin - a);
+            let n = perpendicular(ray.dir);
+            quadratic_roots(
+                n.magnitude_squared(),
+                m.dot(n),
+                m.magnitude_squared() - self.radius * self.radius,
+                &mut candidates[4..6],
+            );
+        }
+
+        // Only candidates on the surface of the union are real hits
+        let tolerance = self.radius * 1e-4 + 1e-6;
+        candidates
+            .into_iter()
+            .filter(|t| *t >= 0.0)
+            .map(|t| (t, ray.at(t)))
+            .filter(|(_, point)| {
+                (self.segment.distance_to_point(*point) - self.radius).abs() <= tolerance
+            })
+            .min_by(|(t0, _), (t1, _)| t0.total_cmp(t1))
+            .map(|(t, point)| RayHit {
+                t,
+                point,
+                normal: (point - self.segment.closest_point(point)) / self.radius,
+            })
+    }
+}
+
+/// Writes the distances at which the ray crosses the sphere surface
+fn sphere_roots(ray: &Ray3f32, center: Vec3f32, radius: f32, out: &mut [f32]) {
+    let offset = ray.origin - center;
+    quadratic_roots(
+        ray.dir.magnitude_squared(),
+        offset.dot(ray.dir),
+        offset.magnitude_squared() - radius * radius,
+        out,
+    );
+}
+
+/// Writes the real roots of a * t^2 + 2 * half_b * t + c = 0 into out,
+/// leaves out untouched if there are none
+fn quadratic_roots(a: f32, half_b: f32, c: f32, out: &mut [f32]) {
+    let discriminant = half_b * half_b - a * c;
+    if a == 0.0 || discriminant < 0.0 {
+        return;
+    }
+    let sqrt_discriminant = float::sqrt(discriminant);
+    out[0] = (-half_b - sqrt_discriminant) / a;
+    out[1] = (-half_b + sqrt_discriminant) / a;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capsule() -> Capsule3f32 {
+        Capsule3f32::new(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 2.0, 0.0),
+            0.5,
+        )
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let capsule = capsule();
+        assert!((2.9999..3.0001).contains(&capsule.length()));
+        assert!(capsule.contains_point(Vec3f32::new(0.4, 1.0, 0.0)));
+        assert!(!capsule.contains_point(Vec3f32::new(0.4, 2.4, 0.0)));
+
+        let other = Capsule3f32::new(
+            Vec3f32::new(0.9, 1.0, -1.0),
+            Vec3f32::new(0.9, 1.0, 1.0),
+            0.5,
+        );
+        assert!(capsule.intersects_capsule(&other));
+        let other = Capsule3f32::new(
+            Vec3f32::new(1.1, 1.0, -1.0),
+            Vec3f32::new(1.1, 1.0, 1.0),
+            0.5,
+        );
+        assert!(!capsule.intersects_capsule(&other));
+
+        assert!(capsule.intersects_sphere(&Sphere::new(Vec3f32::new(0.0, 3.0, 0.0), 0.6)));
+        assert!(!capsule.intersects_sphere(&Sphere::new(Vec3f32::new(0.0, 3.0, 0.0), 0.4)));
+    }
+
+    #[test]
+    fn test_intersect_ray() {
+        let capsule = capsule();
+
+        // Hits the cylinder body
+        let ray = Ray3f32::new(Vec3f32::new(-5.0, 1.0, 0.0), Vec3f32::new(1.0, 0.0, 0.0));
+        let hit = capsule.intersect_ray(&ray).unwrap();
+        assert!((4.4999..4.5001).contains(&hit.t));
+        assert!((-1.0001..-0.9999).contains(&hit.normal.x));
+
+        // Hits the top cap
+        let ray = Ray3f32::new(Vec3f32::new(0.0, 5.0, 0.0), Vec3f32::new(0.0, -1.0, 0.0));
+        let hit = capsule.intersect_ray(&ray).unwrap();
+        assert!((2.4999..2.5001).contains(&hit.t));
+        assert!((0.9999..1.0001).contains(&hit.normal.y));
+
+        // From inside, the exit point is hit
+        let ray = Ray3f32::new(Vec3f32::new(0.0, 1.0, 0.0), Vec3f32::new(0.0, 1.0, 0.0));
+        let hit = capsule.intersect_ray(&ray).unwrap();
+        assert!((1.4999..1.5001).contains(&hit.t));
+
+        let ray = Ray3f32::new(Vec3f32::new(-5.0, 3.0, 0.0), Vec3f32::new(1.0, 0.0, 0.0));
+        assert!(capsule.intersect_ray(&ray).is_none());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    float,
+    geometry::{frustum::Frustum, sphere::Sphere},
+    math,
+    vec::vec3::Vec3f32,
+};
+use std::f32::consts::FRAC_PI_2;
+
+/// A solid cone from apex along axis, with the half opening angle (in
+/// radians) and the height of the cone along the axis. Bounds spot lights,
+/// and the normals of a meshlet as a normal cone (see from_normals).
+#[derive(Debug, Clone, Copy)]
+pub struct Cone {
+    pub apex: Vec3f32,
+    /// Normalized direction from the apex to the center of the base
+    pub axis: Vec3f32,
+    pub angle: f32,
+    pub height: f32,
+}
+
+impl Cone {
+    /// Make sure axis is normalized
+    pub fn new(apex: Vec3f32, axis: Vec3f32, angle: f32, height: f32) -> Self {
+        Self {
+            apex,
+            axis,
+            angle,
+            height,
+        }
+    }
+
+    /// Returns a normal cone (apex at the origin, height 1) containing all
+    /// normals, with the average normal as axis. This is not the tightest
+    /// cone, but close to it for the normals of a meshlet. Zero normals (from
+    /// degenerate triangles) are skipped. Returns None if there are no other
+    /// normals or the normals cancel out.
+    pub fn from_normals(normals: &[Vec3f32]) -> Option<Self> {
+        let unit_normals = normals.iter().filter_map(|&n| {
+            let mag = n.magnitude();
+            (mag > 0.0).then(|| n / mag)
+        });
+        let sum: Vec3f32 = unit_normals.clone().sum();
+        let mag = sum.magnitude();
+        if mag.is_nan() || mag <= f32::EPSILON {
+            return None;
+        }
+        let axis = sum / mag;
+        let min_cos = unit_normals.map(|n| axis.dot(n)).fold(1.0, f32::min);
+        let origin = Vec3f32::new(0.0, 0.0, 0.0);
+        Some(Self::new(origin, axis, math::safe_acos(min_cos), 1.0))
+    }
+
+    /// Returns the radius of the base
+    pub fn radius(&self) -> f32 {
+        self.height * float::tan(self.angle)
+    }
+
+    /// Returns true if p is inside or on the surface of the cone
+    pub fn contains_point(&self, p: Vec3f32) -> bool {
+        let offset = p - self.apex;
+        let along = offset.dot(self.axis);
+        if along < 0.0 || along > self.height {
+            return false;
+        }
+        let perpendicular = (offset - self.axis * along).magnitude();
+        perpendicular <= along * float::tan(self.angle)
+    }
+
+    /// Treats self as the normal cone of a cluster whose positions are
+    /// bounded by bounds, apex and height are ignored. Returns true if every
+    /// triangle in the cluster faces away from view_position, so the whole
+    /// cluster can be culled. Triangles are front facing on the side their
+    /// normals point to.
+    pub fn backfacing(&self, bounds: &Sphere, view_position: Vec3f32) -> bool {
+        if self.angle >= FRAC_PI_2 {
+            return false;
+        }
+        // Every normal has to point away from every point of the sphere,
+        // seen from view_position: the view direction to the center minus
+        // the cone angle has to stay below 90 degrees by the sphere radius
+        let view = bounds.center - view_position;
+        let distance = view.magnitude();
+        if distance <= bounds.radius {
+            return false;
+        }
+        let cos_view = view.dot(self.axis) / distance;
+        let sin_view = float::sqrt((1.0 - cos_view * cos_view).max(0.0));
+        let (sin, cos) = float::sin_cos(self.angle);
+        distance * (cos_view * cos - sin_view * sin) >= bounds.radius
+    }
+
+    /// Returns false if the cone is completely outside of one frustum plane.
+    /// Conservative like the sphere test, see Intersects<Sphere> for Frustum.
+    pub fn intersects_frustum(&self, frustum: &Frustum) -> bool {
+        let base = self.apex + self.axis * self.height;
+        let radius = self.radius();
+        frustum.planes.iter().all(|plane| {
+            // The point of the base circle farthest along the plane normal
+            let cos = plane.normal.dot(self.axis);
+            let sin = float::sqrt((1.0 - cos * cos).max(0.0));
+            let base_distance = plane.signed_distance(base) + radius * sin;
+            plane.signed_distance(self.apex) >= 0.0 || base_distance >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mat::mat4f32::Mat4f32;
+
+    #[test]
+    fn test_normal_cone() {
+        let normals = [
+            Vec3f32::new(0.0, 0.0, 1.0),
+            Vec3f32::new(0.2, 0.0, 1.0),
+            Vec3f32::new(0.0, -0.3, 2.0),
+        ];
+        let cone = Cone::from_normals(&normals).unwrap();
+        for n in normals {
+            assert!(cone.axis.dot(n) / n.magnitude() >= cone.angle.cos() - 0.0001);
+        }
+        assert!(cone.angle < 0.3);
+        assert!(Cone::from_normals(&[]).is_none());
+
+        let zero = Vec3f32::new(0.0, 0.0, 0.0);
+        let with_zero = Cone::from_normals(&[normals[0], zero]).unwrap();
+        assert!((0.9999..1.0001).contains(&with_zero.axis.z));
+        assert!(with_zero.angle < 0.001);
+        assert!(Cone::from_normals(&[zero]).is_none());
+
+        // The cluster faces +z, so it is back facing when seen from below
+        let bounds = Sphere::new(Vec3f32::new(0.0, 0.0, 0.0), 1.0);
+        assert!(cone.backfacing(&bounds, Vec3f32::new(0.0, 0.0, -10.0)));
+        assert!(!cone.backfacing(&bounds, Vec3f32::new(0.0, 0.0, 10.0)));
+        assert!(!cone.backfacing(&bounds, Vec3f32::new(10.0, 0.0, -0.5)));
+        assert!(!cone.backfacing(&bounds, Vec3f32::new(0.0, 0.0, -0.5)));
+    }
+
+    #[test]
+    fn test_cone() {
+        let down = Vec3f32::new(0.0, -1.0, 0.0);
+        let cone = Cone::new(Vec3f32::new(0.0, 5.0, 0.0), down, 0.5, 4.0);
+        assert!(cone.contains_point(Vec3f32::new(0.0, 2.0, 0.0)));
+        assert!(cone.contains_point(Vec3f32::new(1.5, 1.5, 0.0)));
+        assert!(!cone.contains_point(Vec3f32::new(1.5, 4.0, 0.0)));
+        assert!(!cone.contains_point(Vec3f32::new(0.0, 0.5, 0.0)));
+
+        // Camera at the origin looking down -z
+        let frustum = Frustum::from_view_proj(&Mat4f32::perspective(1.0, 1.0, 0.1, 50.0)).unwrap();
+        // Apex outside to the right, but the base reaches into the frustum
+        let reaching = Cone::new(
+            Vec3f32::new(12.0, 0.0, -10.0),
+            Vec3f32::new(-1.0, 0.0, 0.0),
+            0.6,
+            6.0,
+        );
+        assert!(reaching.intersects_frustum(&frustum));
+        let away = Cone::new(
+            Vec3f32::new(12.0, 0.0, -10.0),
+            Vec3f32::new(1.0, 0.0, 0.0),
+            0.6,
+            6.0,
+        );
+        assert!(!away.intersects_frustum(&frustum));
+        let behind = Cone::new(
+            Vec3f32::new(0.0, 0.0, 5.0),
+            Vec3f32::new(0.0, 0.0, 1.0),
+            1.2,
+            3.0,
+        );
+        assert!(!behind.intersects_frustum(&frustum));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{float, mat::mat4f32::Mat4f32, vec::vec3::Vec3f32};
+
+/// A flat circular disk in 3D space
+#[derive(Debug, Clone, Copy)]
+pub struct Disk {
+    pub center: Vec3f32,
+    /// Make sure this is normalized
+    pub normal: Vec3f32,
+    pub radius: f32,
+}
+
+impl Disk {
+    pub fn new(center: Vec3f32, normal: Vec3f32, radius: f32) -> Self {
+        Self {
+            center,
+            normal,
+            radius,
+        }
+    }
+
+    /// Returns true if p lies on the disk, allowing a distance of epsilon
+    /// to the plane of the disk
+    pub fn contains_point(&self, p: Vec3f32, epsilon: f32) -> bool {
+        let offset = p - self.center;
+        let height = offset.dot(self.normal);
+        if height.abs() > epsilon {
+            return false;
+        }
+        (offset - self.normal * height).magnitude_squared() <= self.radius * self.radius
+    }
+
+    /// Returns the area of one side of the disk
+    pub fn surface_area(&self) -> f32 {
+        std::f32::consts::PI * self.radius * self.radius
+    }
+
+    /// Returns the point on the disk closest to p
+    pub fn closest_point(&self, p: Vec3f32) -> Vec3f32 {
+        let offset = p - self.center;
+        let in_plane = offset - self.normal * offset.dot(self.normal);
+        let dist = in_plane.magnitude();
+        if dist <= self.radius {
+            return self.center + in_plane;
+        }
+        self.center + in_plane * (self.radius / dist)
+    }
+
+    /// Returns the disk transformed by m. Non-uniform scale and shear turn a
+    /// disk into an ellipse, in that case the result is the smallest disk
+    /// that contains it (its radius is the major radius of the ellipse).
+    pub fn transformed(&self, m: &Mat4f32) -> Self {
+        let mut result = *self;
+        result.transform(m);
+        result
+    }
+
+    /// Transforms the disk by m (see transformed)
+    pub fn transform(&mut self, m: &Mat4f32) {
+        // Normals transform with the cofactor matrix (inverse transpose up to scale)
+        let c0 = m.transform_vector(Vec3f32::new(1.0, 0.0, 0.0));
+        let c1 = m.transform_vector(Vec3f32::new(0.0, 1.0, 0.0));
+        let c2 = m.transform_vector(Vec3f32::new(0.0, 0.0, 1.0));
+        let normal = c1.cross(c2) * self.normal.x
+            + c2.cross(c0) * self.normal.y
+            + c0.cross(c1) * self.normal.z;
+
+        // The ellipse spanned by two perpendicular radii in the plane of the
+        // disk has the largest singular value of [m*u m*v] as major radius,
+        // the square root of the larger eigenvalue of their Gram matrix
+        let (u, v) = self.normal.orthonormal_basis();
+        let (mu, mv) = (m.transform_vector(u), m.transform_vector(v));
+        let (a, b, c) = (mu.dot(mu), mu.dot(mv), mv.dot(mv));
+        let half_diff = (a - c) / 2.0;
+        let max_eigenvalue = (a + c) / 2.0 + float::sqrt(half_diff * half_diff + b * b);
+
+        self.center = m.transform_point(self.center);
+        self.normal = normal / normal.magnitude();
+        self.radius *= float::sqrt(max_eigenvalue);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_and_closest_point() {
+        let disk = Disk::new(
+            Vec3f32::new(0.0, 1.0, 0.0),
+            Vec3f32::new(0.0, 1.0, 0.0),
+            2.0,
+        );
+        assert!(disk.contains_point(Vec3f32::new(1.0, 1.0, 1.0), 0.0001));
+        assert!(!disk.contains_point(Vec3f32::new(1.0, 1.1, 1.0), 0.0001));
+        assert!(!disk.contains_point(Vec3f32::new(2.0, 1.0, 2.0), 0.0001));
+
+        let p = disk.closest_point(Vec3f32::new(4.0, 3.0, 0.0));
+        assert!((1.9999..2.0001).contains(&p.x));
+        assert!((0.9999..1.0001).contains(&p.y));
+        assert!((-0.0001..0.0001).contains(&p.z));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_transformed_with_scale() {
+        // Scales x by 2, the normal of a tilted disk has to tilt towards x
+        let m = Mat4f32 { values: [
+            2.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]};
+        let mut normal = Vec3f32::new(1.0, 1.0, 0.0);
+        normal.normalize();
+        let disk = Disk::new(Vec3f32::new(1.0, 0.0, 0.0), normal, 1.0).transformed(&m);
+
+        // A direction inside the disk must stay perpendicular to the normal
+        let tangent = m.transform_vector(Vec3f32::new(1.0, -1.0, 0.0));
+        assert!((-0.0001..0.0001).contains(&tangent.dot(disk.normal)));
+        assert!((1.9999..2.0001).contains(&disk.center.x));
+        // The radius along (1, -1, 0) grows to sqrt(2.5), the one along z stays 1
+        assert!((1.5810..1.5812).contains(&disk.radius));
+    }
+
+    #[test]
+    fn test_transformed_with_shear_and_normal_scale() {
+        let disk = Disk::new(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 0.0, 1.0),
+            1.0,
+        );
+        let mut shear = Mat4f32::identity();
+        shear.values[1] = 1.0;
+        // The rim of the sheared disk reaches the golden ratio
+        let sheared = disk.transformed(&shear);
+        assert!((1.6180..1.6181).contains(&sheared.radius));
+
+        // Scaling along the normal leaves the disk unchanged
+        let mut flat = Mat4f32::identity();
+        flat.values[10] = 10.0;
+        assert!((0.9999..1.0001).contains(&disk.transformed(&flat).radius));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{geometry::plane::Plane, mat::mat4f32::Mat4f32, vec::vec3::Vec3f32};
+
+/// A view frustum described by 6 planes with normals pointing inwards
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    /// Left, right, bottom, top, near, far
+    pub planes: [Plane; 6],
+    /// The 8 corners in world space, bit 0 of the index selects the right
+    /// side, bit 1 the top side and bit 2 the far side
+    pub corners: [Vec3f32; 8],
+}
+
+impl Frustum {
+    /// Extracts the frustum planes from a view projection matrix
+    /// (Gribb/Hartmann). Expects clip space depth to range from 0 to 1.
+    /// Returns None if the matrix is not invertible.
+    pub fn from_view_proj(m: &Mat4f32) -> Option<Self> {
+        let inverse = m.inverse()?;
+
+        let v = &m.values;
+        let row = |i: usize| [v[i * 4], v[i * 4 + 1], v[i * 4 + 2], v[i * 4 + 3]];
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+
+        let plane = |c: [f32; 4]| {
+            let mut plane = Plane::new(Vec3f32::new(c[0], c[1], c[2]), c[3]);
+            plane.normalize();
+            plane
+        };
+        let add = |a: [f32; 4], b: [f32; 4]| std::array::from_fn(|i| a[i] + b[i]);
+        let sub = |a: [f32; 4], b: [f32; 4]| std::array::from_fn(|i| a[i] - b[i]);
+
+        Some(Self {
+            planes: [
+                plane(add(r3, r0)),
+                plane(sub(r3, r0)),
+                plane(add(r3, r1)),
+                plane(sub(r3, r1)),
+                plane(r2),
+                plane(sub(r3, r2)),
+            ],
+            corners: Self::corners_from_inverse_view_proj(&inverse),
+        })
+    }
+
+    /// Returns the 8 world space corners of the frustum of a view projection
+    /// matrix, given its inverse. Expects clip space depth to range from 0 to 1.
+    /// Uses the same order as Frustum::corners.
+    pub fn corners_from_inverse_view_proj(inv_view_proj: &Mat4f32) -> [Vec3f32; 8] {
+        std::array::from_fn(|i| {
+            let ndc = Vec3f32::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { 0.0 } else { 1.0 },
+            );
+            inv_view_proj.transform_point_projective(ndc)
+        })
+    }
+
+    /// Returns true if p is inside or on the boundary of the frustum
+    pub fn contains_point(&self, p: Vec3f32) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(p) >= 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_from_orthographic() {
+        // Orthographic projection of the box x, y in -1..1 and z in -1..-3
+        let m = Mat4f32 { values: [
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, -0.5, -0.5,
+            0.0, 0.0, 0.0, 1.0,
+        ]};
+        let frustum = Frustum::from_view_proj(&m).unwrap();
+        assert!(frustum.contains_point(Vec3f32::new(0.5, -0.5, -2.0)));
+        assert!(!frustum.contains_point(Vec3f32::new(0.5, -0.5, 0.0)));
+        assert!(!frustum.contains_point(Vec3f32::new(0.5, -0.5, -4.0)));
+        assert!(!frustum.contains_point(Vec3f32::new(1.5, -0.5, -2.0)));
+
+        let far_top_right = frustum.corners[7];
+        assert!((0.9999..1.0001).contains(&far_top_right.x));
+        assert!((0.9999..1.0001).contains(&far_top_right.y));
+        assert!((-3.0001..-2.9999).contains(&far_top_right.z));
+    }
+}
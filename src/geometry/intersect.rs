@@ -0,0 +1,236 @@
+use crate::geometry::{
+    aabb3f32::Aabb3f32, capsule3f32::Capsule3f32, cone::Cone, frustum::Frustum, obb3f32::Obb3f32,
+    queries, sphere::Sphere,
+};
+
+/// Overlap test between two primitives. Touching primitives intersect.
+pub trait Intersects<Rhs = Self> {
+    fn intersects(&self, other: &Rhs) -> bool;
+}
+
+/// Returns true if a and b overlap, works for every pair of primitives
+/// that implements Intersects (in both orders)
+pub fn intersects<A: Intersects<B>, B>(a: &A, b: &B) -> bool {
+    a.intersects(b)
+}
+
+/// impl_symmetric!(A, B) implements Intersects<A> for B using the Intersects<B> impl of A
+macro_rules! impl_symmetric {
+    ($a:ty, $b:ty) => {
+        impl Intersects<$a> for $b {
+            fn intersects(&self, other: &$a) -> bool {
+                other.intersects(self)
+            }
+        }
+    };
+}
+
+impl Intersects for Sphere {
+    fn intersects(&self, other: &Sphere) -> bool {
+        let radius = self.radius + other.radius;
+        (self.center - other.center).magnitude_squared() <= radius * radius
+    }
+}
+
+impl Intersects<Aabb3f32> for Sphere {
+    fn intersects(&self, other: &Aabb3f32) -> bool {
+        self.contains_point(queries::closest_point_on_aabb(self.center, other))
+    }
+}
+impl_symmetric!(Sphere, Aabb3f32);
+
+impl Intersects for Aabb3f32 {
+    fn intersects(&self, other: &Aabb3f32) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+            && self.min.z <= other.max.z
+            && self.max.z >= other.min.z
+    }
+}
+
+impl Intersects for Obb3f32 {
+    fn intersects(&self, other: &Obb3f32) -> bool {
+        self.intersects_obb(other)
+    }
+}
+
+impl Intersects<Aabb3f32> for Obb3f32 {
+    fn intersects(&self, other: &Aabb3f32) -> bool {
+        self.intersects_aabb(other)
+    }
+}
+impl_symmetric!(Obb3f32, Aabb3f32);
+
+impl Intersects<Sphere> for Obb3f32 {
+    fn intersects(&self, other: &Sphere) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+impl_symmetric!(Obb3f32, Sphere);
+
+/// Conservative: spheres near the frustum corners that are outside of the
+/// frustum but not completely outside of a single plane are reported as intersecting.
+impl Intersects<Sphere> for Frustum {
+    fn intersects(&self, other: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(other.center) >= -other.radius)
+    }
+}
+impl_symmetric!(Frustum, Sphere);
+
+/// Rejects boxes completely outside of one frustum plane, and frustums
+/// completely outside of one box face. The second test fixes the false
+/// positives of large boxes behind a frustum corner that the plane test
+/// alone produces. The result is still conservative in rare edge-edge cases.
+impl Intersects<Aabb3f32> for Frustum {
+    fn intersects(&self, other: &Aabb3f32) -> bool {
+        let center = other.center();
+        let extents = other.half_extents();
+        let outside_plane = self.planes.iter().any(|plane| {
+            // Projected radius of the box onto the plane normal
+            let radius = plane.normal.x.abs() * extents.x
+                + plane.normal.y.abs() * extents.y
+                + plane.normal.z.abs() * extents.z;
+            plane.signed_distance(center) < -radius
+        });
+        if outside_plane {
+            return false;
+        }
+
+        let corners = &self.corners;
+        !(corners.iter().all(|c| c.x > other.max.x)
+            || corners.iter().all(|c| c.x < other.min.x)
+            || corners.iter().all(|c| c.y > other.max.y)
+            || corners.iter().all(|c| c.y < other.min.y)
+            || corners.iter().all(|c| c.z > other.max.z)
+            || corners.iter().all(|c| c.z < other.min.z))
+    }
+}
+impl_symmetric!(Frustum, Aabb3f32);
+
+/// See Obb3f32::intersects_frustum
+impl Intersects<Obb3f32> for Frustum {
+    fn intersects(&self, other: &Obb3f32) -> bool {
+        other.intersects_frustum(self)
+    }
+}
+impl_symmetric!(Frustum, Obb3f32);
+
+/// See Cone::intersects_frustum
+impl Intersects<Cone> for Frustum {
+    fn intersects(&self, other: &Cone) -> bool {
+        other.intersects_frustum(self)
+    }
+}
+impl_symmetric!(Frustum, Cone);
+
+impl Intersects for Capsule3f32 {
+    fn intersects(&self, other: &Capsule3f32) -> bool {
+        self.intersects_capsule(other)
+    }
+}
+
+impl Intersects<Sphere> for Capsule3f32 {
+    fn intersects(&self, other: &Sphere) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+impl_symmetric!(Capsule3f32, Sphere);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mat::mat4f32::Mat4f32, rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32};
+
+    fn unit_aabb(center: Vec3f32) -> Aabb3f32 {
+        let half = Vec3f32::new(1.0, 1.0, 1.0);
+        Aabb3f32::new(center - half, center + half)
+    }
+
+    /// Symmetric perspective frustum looking down -z with a 90 degree
+    /// field of view, near 1 and far 10 (depth 0..1)
+    #[rustfmt::skip]
+    fn frustum() -> Frustum {
+        let (near, far) = (1.0, 10.0);
+        let m = Mat4f32 { values: [
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, far / (near - far), near * far / (near - far),
+            0.0, 0.0, -1.0, 0.0,
+        ]};
+        Frustum::from_view_proj(&m).unwrap()
+    }
+
+    #[test]
+    fn test_sphere_and_aabb() {
+        let a = Sphere::new(Vec3f32::new(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(Vec3f32::new(1.9, 0.0, 0.0), 1.0);
+        let c = Sphere::new(Vec3f32::new(2.1, 0.0, 0.0), 1.0);
+        assert!(intersects(&a, &b));
+        assert!(!intersects(&a, &c));
+
+        let aabb = unit_aabb(Vec3f32::new(0.0, 0.0, 0.0));
+        assert!(intersects(
+            &aabb,
+            &Sphere::new(Vec3f32::new(1.5, 1.5, 0.0), 0.75)
+        ));
+        assert!(!intersects(
+            &Sphere::new(Vec3f32::new(1.5, 1.5, 0.0), 0.7),
+            &aabb
+        ));
+
+        assert!(intersects(&aabb, &unit_aabb(Vec3f32::new(2.0, 0.0, 0.0))));
+        assert!(!intersects(&aabb, &unit_aabb(Vec3f32::new(2.1, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn test_obb() {
+        let obb = Obb3f32::new(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 1.0, 1.0),
+            Rot3Df32::identity(),
+        );
+        let aabb = unit_aabb(Vec3f32::new(1.5, 0.0, 0.0));
+        assert!(intersects(&obb, &aabb));
+        assert!(intersects(&aabb, &obb));
+        assert!(intersects(&obb, &obb));
+    }
+
+    #[test]
+    fn test_frustum() {
+        let frustum = frustum();
+        assert!(intersects(
+            &frustum,
+            &Sphere::new(Vec3f32::new(0.0, 0.0, -5.0), 1.0)
+        ));
+        assert!(!intersects(
+            &frustum,
+            &Sphere::new(Vec3f32::new(0.0, 0.0, 1.0), 0.5)
+        ));
+        assert!(intersects(
+            &frustum,
+            &unit_aabb(Vec3f32::new(0.0, 0.0, -5.0))
+        ));
+        assert!(!intersects(
+            &frustum,
+            &unit_aabb(Vec3f32::new(0.0, 0.0, 5.0))
+        ));
+    }
+
+    #[test]
+    fn test_frustum_large_box_behind_corner() {
+        // A large box diagonally outside of the far right corner, it straddles
+        // the right and far planes so the plane test alone accepts it
+        let frustum = frustum();
+        let aabb = Aabb3f32::new(
+            Vec3f32::new(10.5, -20.0, -30.0),
+            Vec3f32::new(40.0, 20.0, -9.5),
+        );
+        assert!(!intersects(&frustum, &aabb));
+        let obb = Obb3f32::from_aabb(&aabb);
+        assert!(!intersects(&frustum, &obb));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{geometry::plane::Plane, vec::vec3::Vec3f32};
+
+/// An infinite line in Plücker coordinates, the direction and the moment
+/// (any point on the line crossed with the direction). Unlike a point and
+/// a direction, this representation is unique up to scale and makes
+/// sidedness tests between lines a single product.
+#[derive(Debug, Clone, Copy)]
+pub struct Line3f32 {
+    pub dir: Vec3f32,
+    pub moment: Vec3f32,
+}
+
+impl Line3f32 {
+    /// Construct the line through point along dir.
+    /// Make sure dir is not 0.
+    pub fn new(point: Vec3f32, dir: Vec3f32) -> Self {
+        Self {
+            dir,
+            moment: point.cross(dir),
+        }
+    }
+
+    /// Construct the line through a and b, pointing from a to b.
+    /// Make sure a and b are different.
+    pub fn from_points(a: Vec3f32, b: Vec3f32) -> Self {
+        Self::new(a, b - a)
+    }
+
+    /// Returns the point on the line closest to the origin
+    pub fn point(&self) -> Vec3f32 {
+        self.dir.cross(self.moment) / self.dir.magnitude_squared()
+    }
+
+    /// Returns the point on the line closest to p
+    pub fn closest_point(&self, p: Vec3f32) -> Vec3f32 {
+        let origin = self.point();
+        origin + self.dir * ((p - origin).dot(self.dir) / self.dir.magnitude_squared())
+    }
+
+    /// Returns the distance between p and the line
+    pub fn distance_to_point(&self, p: Vec3f32) -> f32 {
+        (p.cross(self.dir) - self.moment).magnitude() / self.dir.magnitude()
+    }
+
+    /// Returns the permuted inner product of both lines. It is 0 if the
+    /// lines intersect or are parallel, otherwise its sign tells on which
+    /// side of self other passes.
+    pub fn side(&self, other: &Line3f32) -> f32 {
+        self.dir.dot(other.moment) + other.dir.dot(self.moment)
+    }
+
+    /// Returns the closest points between self and other, the first point
+    /// is on self and the second on other. Returns None if the lines are
+    /// parallel, because then every point has a closest partner.
+    pub fn closest_points(&self, other: &Line3f32) -> Option<(Vec3f32, Vec3f32)> {
+        let n = self.dir.cross(other.dir);
+        let n_sqrd = n.magnitude_squared();
+        if n_sqrd <= f32::EPSILON * self.dir.magnitude_squared() * other.dir.magnitude_squared() {
+            return None;
+        }
+        let (p, q) = (self.point(), other.point());
+        let t = (q - p).cross(other.dir).dot(n) / n_sqrd;
+        let s = (q - p).cross(self.dir).dot(n) / n_sqrd;
+        Some((p + self.dir * t, q + other.dir * s))
+    }
+
+    /// Returns the distance between self and other
+    pub fn distance_to_line(&self, other: &Line3f32) -> f32 {
+        match self.closest_points(other) {
+            Some((p, q)) => p.distance(q),
+            None => self.distance_to_point(other.point()),
+        }
+    }
+
+    /// Returns the point where the line crosses the plane, or None if they
+    /// are parallel
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<Vec3f32> {
+        let denom = plane.normal.dot(self.dir);
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+        Some((plane.normal.cross(self.moment) - self.dir * plane.d) / denom)
+    }
+
+    /// Returns true if the line passes through the triangle a, b, c (from
+    /// either side), using the sides of the line against the triangle
+    /// edges. Lines through an edge or vertex count as passing through.
+    pub fn intersects_triangle(&self, a: Vec3f32, b: Vec3f32, c: Vec3f32) -> bool {
+        let sides = [
+            self.side(&Line3f32::from_points(a, b)),
+            self.side(&Line3f32::from_points(b, c)),
+            self.side(&Line3f32::from_points(c, a)),
+        ];
+        sides.iter().all(|&s| s >= 0.0) || sides.iter().all(|&s| s <= 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line() {
+        let line = Line3f32::new(Vec3f32::new(5.0, 1.0, 2.0), Vec3f32::new(2.0, 0.0, 0.0));
+        let p = line.point();
+        assert!((-0.0001..0.0001).contains(&p.distance(Vec3f32::new(0.0, 1.0, 2.0))));
+        let q = line.closest_point(Vec3f32::new(-3.0, 4.0, 2.0));
+        assert!((-0.0001..0.0001).contains(&q.distance(Vec3f32::new(-3.0, 1.0, 2.0))));
+        assert!((2.9999..3.0001).contains(&line.distance_to_point(Vec3f32::new(7.0, 4.0, 2.0))));
+
+        let plane = Plane::new(Vec3f32::new(1.0, 0.0, 0.0), -4.0);
+        let hit = line.intersect_plane(&plane).unwrap();
+        assert!((-0.0001..0.0001).contains(&hit.distance(Vec3f32::new(4.0, 1.0, 2.0))));
+        let parallel = Plane::new(Vec3f32::new(0.0, 1.0, 0.0), 0.0);
+        assert!(line.intersect_plane(&parallel).is_none());
+    }
+
+    #[test]
+    fn test_closest_points() {
+        let a = Line3f32::from_points(Vec3f32::new(0.0, 0.0, 0.0), Vec3f32::new(1.0, 0.0, 0.0));
+        let b = Line3f32::from_points(Vec3f32::new(3.0, 2.0, -1.0), Vec3f32::new(3.0, 2.0, 1.0));
+        let (p, q) = a.closest_points(&b).unwrap();
+        assert!((-0.0001..0.0001).contains(&p.distance(Vec3f32::new(3.0, 0.0, 0.0))));
+        assert!((-0.0001..0.0001).contains(&q.distance(Vec3f32::new(3.0, 2.0, 0.0))));
+        assert!((1.9999..2.0001).contains(&a.distance_to_line(&b)));
+
+        let parallel = Line3f32::new(Vec3f32::new(0.0, 3.0, 0.0), Vec3f32::new(-2.0, 0.0, 0.0));
+        assert!(a.closest_points(&parallel).is_none());
+        assert!((2.9999..3.0001).contains(&a.distance_to_line(&parallel)));
+
+        // Opposite sides for lines passing on either side
+        let above = Line3f32::new(Vec3f32::new(0.0, 1.0, 0.0), Vec3f32::new(0.0, 0.0, 1.0));
+        let below = Line3f32::new(Vec3f32::new(0.0, -1.0, 0.0), Vec3f32::new(0.0, 0.0, 1.0));
+        assert!(a.side(&above) * a.side(&below) < 0.0);
+        let crossing = Line3f32::new(Vec3f32::new(2.0, 0.0, 0.0), Vec3f32::new(0.0, 0.0, 1.0));
+        assert!((-0.0001..0.0001).contains(&a.side(&crossing)));
+    }
+
+    #[test]
+    fn test_intersects_triangle() {
+        let (a, b, c) = (
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(2.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 2.0, 0.0),
+        );
+        let down = Vec3f32::new(0.0, 0.0, -1.0);
+        assert!(Line3f32::new(Vec3f32::new(0.5, 0.5, 3.0), down).intersects_triangle(a, b, c));
+        assert!(Line3f32::new(Vec3f32::new(0.5, 0.5, 3.0), -down).intersects_triangle(a, b, c));
+        assert!(!Line3f32::new(Vec3f32::new(1.5, 1.5, 3.0), down).intersects_triangle(a, b, c));
+    }
+}
@@ -0,0 +1,19 @@
+pub mod aabb3f32;
+pub mod capsule3f32;
+pub mod cone;
+pub mod disk;
+pub mod frustum;
+pub mod intersect;
+pub mod line3f32;
+pub mod obb3f32;
+pub mod plane;
+pub mod points;
+pub mod polygon;
+pub mod predicates;
+pub mod queries;
+pub mod ray3f32;
+pub mod rect2f32;
+pub mod segment3f32;
+pub mod sphere;
+pub mod tangent;
+pub mod triangle;
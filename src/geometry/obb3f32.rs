@@ -0,0 +1,283 @@
+use crate::{
+    geometry::{aabb3f32::Aabb3f32, frustum::Frustum, points, sphere::Sphere},
+    rotor::rot3df32::Rot3Df32,
+    vec::vec3::Vec3f32,
+};
+
+/// Oriented bounding box
+#[derive(Debug, Clone, Copy)]
+pub struct Obb3f32 {
+    pub center: Vec3f32,
+    /// Half the size of the box along each of its local axes
+    pub half_extents: Vec3f32,
+    /// Rotates the local axes of the box into world space
+    pub orientation: Rot3Df32,
+}
+
+impl Obb3f32 {
+    pub fn new(center: Vec3f32, half_extents: Vec3f32, orientation: Rot3Df32) -> Self {
+        Self {
+            center,
+            half_extents,
+            orientation,
+        }
+    }
+
+    pub fn from_aabb(aabb: &Aabb3f32) -> Self {
+        Self::new(aabb.center(), aabb.half_extents(), Rot3Df32::identity())
+    }
+
+    /// Fits a box to the points by aligning it with the principal axes of
+    /// the point covariance (PCA). This gives tight boxes for elongated
+    /// point sets, but is not guaranteed to be the minimal box.
+    /// Returns a box with zero size at the origin if points is empty.
+    pub fn from_points(points: &[Vec3f32]) -> Self {
+        if points.is_empty() {
+            let origin = Vec3f32::new(0.0, 0.0, 0.0);
+            return Self::new(origin, origin, Rot3Df32::identity());
+        }
+
+        let stats = points::stats(points);
+        let centroid = stats.centroid;
+        let (_, orientation) = stats.covariance.symmetric_eigen();
+        let axes = [
+            orientation.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0)),
+            orientation.rotated_vec(Vec3f32::new(0.0, 1.0, 0.0)),
+            orientation.rotated_vec(Vec3f32::new(0.0, 0.0, 1.0)),
+        ];
+
+        // Extents of the points along the axes
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for &p in points {
+            let offset = p - centroid;
+            for i in 0..3 {
+                let projected = offset.dot(axes[i]);
+                min[i] = min[i].min(projected);
+                max[i] = max[i].max(projected);
+            }
+        }
+
+        let mut center = centroid;
+        for i in 0..3 {
+            center = center + axes[i] * ((min[i] + max[i]) / 2.0);
+        }
+        Self::new(
+            center,
+            Vec3f32::new(
+                (max[0] - min[0]) / 2.0,
+                (max[1] - min[1]) / 2.0,
+                (max[2] - min[2]) / 2.0,
+            ),
+            orientation,
+        )
+    }
+
+    /// Returns the local x, y and z axes of the box in world space
+    pub fn axes(&self) -> [Vec3f32; 3] {
+        [
+            self.orientation.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0)),
+            self.orientation.rotated_vec(Vec3f32::new(0.0, 1.0, 0.0)),
+            self.orientation.rotated_vec(Vec3f32::new(0.0, 0.0, 1.0)),
+        ]
+    }
+
+    /// Returns the 8 corners of the box, bit 0 of the index selects the
+    /// positive local x side, bit 1 the positive y side and bit 2 the positive z side
+    pub fn corners(&self) -> [Vec3f32; 8] {
+        let [x, y, z] = self.axes();
+        let x = x * self.half_extents.x;
+        let y = y * self.half_extents.y;
+        let z = z * self.half_extents.z;
+        std::array::from_fn(|i| {
+            let mut corner = self.center;
+            corner = corner + if i & 1 == 0 { -x } else { x };
+            corner = corner + if i & 2 == 0 { -y } else { y };
+            corner = corner + if i & 4 == 0 { -z } else { z };
+            corner
+        })
+    }
+
+    /// Returns the point inside or on the surface of the box closest to p
+    pub fn closest_point(&self, p: Vec3f32) -> Vec3f32 {
+        let offset = p - self.center;
+        let extents = [
+            self.half_extents.x,
+            self.half_extents.y,
+            self.half_extents.z,
+        ];
+        let mut result = self.center;
+        for (axis, extent) in self.axes().into_iter().zip(extents) {
+            result = result + axis * offset.dot(axis).clamp(-extent, extent);
+        }
+        result
+    }
+
+    /// Returns true if p is inside or on the surface of the box
+    pub fn contains_point(&self, p: Vec3f32) -> bool {
+        let local = self.orientation.inverted().rotated_vec(p - self.center);
+        local.x.abs() <= self.half_extents.x
+            && local.y.abs() <= self.half_extents.y
+            && local.z.abs() <= self.half_extents.z
+    }
+
+    /// Separating axis test between two oriented boxes
+    pub fn intersects_obb(&self, other: &Obb3f32) -> bool {
+        let a = self.axes();
+        let b = other.axes();
+        let ea = [
+            self.half_extents.x,
+            self.half_extents.y,
+            self.half_extents.z,
+        ];
+        let eb = [
+            other.half_extents.x,
+            other.half_extents.y,
+            other.half_extents.z,
+        ];
+
+        // Rotation of other expressed in the frame of self, the epsilon
+        // counters arithmetic errors when edges are (nearly) parallel
+        let r: [[f32; 3]; 3] = std::array::from_fn(|i| std::array::from_fn(|j| a[i].dot(b[j])));
+        let abs_r: [[f32; 3]; 3] =
+            std::array::from_fn(|i| std::array::from_fn(|j| r[i][j].abs() + 1e-6));
+        let offset = other.center - self.center;
+        let t = [offset.dot(a[0]), offset.dot(a[1]), offset.dot(a[2])];
+
+        // Face axes of self
+        for i in 0..3 {
+            let rb = eb[0] * abs_r[i][0] + eb[1] * abs_r[i][1] + eb[2] * abs_r[i][2];
+            if t[i].abs() > ea[i] + rb {
+                return false;
+            }
+        }
+
+        // Face axes of other
+        for j in 0..3 {
+            let ra = ea[0] * abs_r[0][j] + ea[1] * abs_r[1][j] + ea[2] * abs_r[2][j];
+            let dist = t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j];
+            if dist.abs() > ra + eb[j] {
+                return false;
+            }
+        }
+
+        // Cross products of edge directions a[i] x b[j]
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = ea[i1] * abs_r[i2][j] + ea[i2] * abs_r[i1][j];
+                let rb = eb[j1] * abs_r[i][j2] + eb[j2] * abs_r[i][j1];
+                let dist = t[i2] * r[i1][j] - t[i1] * r[i2][j];
+                if dist.abs() > ra + rb {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3f32) -> bool {
+        self.intersects_obb(&Obb3f32::from_aabb(aabb))
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.contains_point(self.closest_point(sphere.center))
+    }
+
+    /// Returns false if the box is completely outside of one of the frustum
+    /// planes or the frustum is completely outside of one of the box faces.
+    /// The second test removes most false positives of large boxes near the
+    /// frustum corners, but the result is still conservative.
+    pub fn intersects_frustum(&self, frustum: &Frustum) -> bool {
+        let axes = self.axes();
+        let extents = [
+            self.half_extents.x,
+            self.half_extents.y,
+            self.half_extents.z,
+        ];
+        let outside_plane = frustum.planes.iter().any(|plane| {
+            let radius: f32 = axes
+                .iter()
+                .zip(extents)
+                .map(|(axis, extent)| plane.normal.dot(*axis).abs() * extent)
+                .sum();
+            plane.signed_distance(self.center) < -radius
+        });
+        if outside_plane {
+            return false;
+        }
+
+        // Frustum corners in the local space of the box
+        let local = frustum
+            .corners
+            .map(|corner| axes.map(|axis| (corner - self.center).dot(axis)));
+        (0..3).all(|i| {
+            !local.iter().all(|corner| corner[i] > extents[i])
+                && !local.iter().all(|corner| corner[i] < -extents[i])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_points_elongated() {
+        // Points along a diagonal rod
+        let mut dir = Vec3f32::new(1.0, 1.0, 0.0);
+        dir.normalize();
+        let side = Vec3f32::new(0.0, 0.0, 1.0);
+        let mut points = Vec::new();
+        for i in -10..=10 {
+            let along = dir * i as f32;
+            points.push(along + side * 0.1);
+            points.push(along - side * 0.1);
+            points.push(along + dir.cross(side) * 0.1);
+            points.push(along - dir.cross(side) * 0.1);
+        }
+
+        let obb = Obb3f32::from_points(&points);
+        let volume = obb.half_extents.x * obb.half_extents.y * obb.half_extents.z * 8.0;
+        // An AABB of the same rod would have a volume of ~(14.2 * 14.2 * 0.2)
+        assert!(volume < 20.0 * 0.2 * 0.2 * 1.01);
+        for &p in &points {
+            assert!(
+                Obb3f32::new(obb.center, obb.half_extents * 1.001, obb.orientation)
+                    .contains_point(p)
+            );
+        }
+    }
+
+    #[test]
+    fn test_intersects_obb() {
+        let mut diagonal = Vec3f32::new(1.0, 1.0, 0.0);
+        diagonal.normalize();
+        let rotation = Rot3Df32::new_exact(Vec3f32::new(1.0, 0.0, 0.0), diagonal);
+        let unit = Vec3f32::new(1.0, 1.0, 1.0);
+
+        let a = Obb3f32::new(Vec3f32::new(0.0, 0.0, 0.0), unit, Rot3Df32::identity());
+        // Rotated by 45 degrees, the corner reaches 1 + sqrt(2) along x
+        let b = Obb3f32::new(Vec3f32::new(2.3, 0.0, 0.0), unit, rotation);
+        let c = Obb3f32::new(Vec3f32::new(2.5, 0.0, 0.0), unit, rotation);
+        assert!(a.intersects_obb(&b));
+        assert!(!a.intersects_obb(&c));
+    }
+
+    #[test]
+    fn test_intersects_sphere_and_aabb() {
+        let obb = Obb3f32::new(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 1.0, 1.0),
+            Rot3Df32::identity(),
+        );
+        assert!(obb.intersects_sphere(&Sphere::new(Vec3f32::new(1.5, 0.0, 0.0), 0.6)));
+        assert!(!obb.intersects_sphere(&Sphere::new(Vec3f32::new(1.5, 1.5, 0.0), 0.6)));
+
+        let aabb = Aabb3f32::new(Vec3f32::new(0.5, 0.5, 0.5), Vec3f32::new(2.0, 2.0, 2.0));
+        assert!(obb.intersects_aabb(&aabb));
+        let aabb = Aabb3f32::new(Vec3f32::new(1.5, 0.5, 0.5), Vec3f32::new(2.0, 2.0, 2.0));
+        assert!(!obb.intersects_aabb(&aabb));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    float,
+    geometry::ray3f32::{Ray3f32, RayHit},
+    mat::mat4f32::Mat4f32,
+    vec::vec3::Vec3f32,
+};
+
+/// The plane of all points p with normal.dot(p) + d = 0
+#[derive(Debug, Clone, Copy)]
+pub struct Plane {
+    pub normal: Vec3f32,
+    pub d: f32,
+}
+
+impl Plane {
+    pub fn new(normal: Vec3f32, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    /// Construct the plane through point with the given normal.
+    /// Make sure the normal is normalized.
+    pub fn from_point_normal(point: Vec3f32, normal: Vec3f32) -> Self {
+        Self::new(normal, -normal.dot(point))
+    }
+
+    /// Construct the plane through the points a, b and c. The normal points
+    /// to the side from which a, b, c appear counter-clockwise.
+    /// Returns None if the points are (nearly) collinear.
+    pub fn from_points(a: Vec3f32, b: Vec3f32, c: Vec3f32) -> Option<Self> {
+        let (ab, ac) = (b - a, c - a);
+        let normal = ab.cross(ac);
+        let mag = normal.magnitude();
+        if mag <= f32::EPSILON * ab.magnitude() * ac.magnitude() {
+            return None;
+        }
+        Some(Self::from_point_normal(a, normal / mag))
+    }
+
+    /// Returns the signed distance of p to the plane, positive on the side
+    /// the normal points to. Only a real distance if the normal is normalized.
+    pub fn signed_distance(&self, p: Vec3f32) -> f32 {
+        self.normal.dot(p) + self.d
+    }
+
+    /// Scales normal and d so that the normal is normalized
+    pub fn normalize(&mut self) {
+        let mag = self.normal.magnitude();
+        self.normal = self.normal / mag;
+        self.d /= mag;
+    }
+
+    /// Returns the orthogonal projection of p onto the plane.
+    /// Make sure the normal is normalized.
+    pub fn project_point(&self, p: Vec3f32) -> Vec3f32 {
+        p - self.normal * self.signed_distance(p)
+    }
+
+    /// Returns the mirror image of p on the other side of the plane.
+    /// Make sure the normal is normalized.
+    pub fn reflect_point(&self, p: Vec3f32) -> Vec3f32 {
+        p - self.normal * (2.0 * self.signed_distance(p))
+    }
+
+    /// Returns the hit of the ray with the plane, see Ray3f32::intersect_plane
+    pub fn intersect_ray(&self, ray: &Ray3f32) -> Option<RayHit> {
+        ray.intersect_plane(self)
+    }
+
+    /// Returns the line where both planes intersect as a point on the line
+    /// and the normalized line direction, or None if the planes are parallel.
+    pub fn intersect_plane(&self, other: &Plane) -> Option<(Vec3f32, Vec3f32)> {
+        let dir = self.normal.cross(other.normal);
+        let dir_mag_sqrd = dir.magnitude_squared();
+        if dir_mag_sqrd <= f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+
+        // The point on the line closest to the origin
+        let point =
+            (other.normal.cross(dir) * -self.d + dir.cross(self.normal) * -other.d) / dir_mag_sqrd;
+        Some((point, dir / float::sqrt(dir_mag_sqrd)))
+    }
+
+    /// Returns the plane transformed by m or None if m is not invertible.
+    /// Planes transform with the inverse transpose of m, if the same matrix
+    /// is used for many planes, compute it once and use
+    /// transformed_by_inverse_transpose instead.
+    pub fn transformed(&self, m: &Mat4f32) -> Option<Self> {
+        let inverse_transpose = m.inverse()?.transposed();
+        Some(self.transformed_by_inverse_transpose(&inverse_transpose))
+    }
+
+    /// Returns the plane transformed by the matrix whose inverse transpose
+    /// is m. The result is normalized.
+    pub fn transformed_by_inverse_transpose(&self, m: &Mat4f32) -> Self {
+        let v = &m.values;
+        let (n, d) = (self.normal, self.d);
+        let mut result = Self::new(
+            Vec3f32::new(
+                v[0] * n.x + v[1] * n.y + v[2] * n.z + v[3] * d,
+                v[4] * n.x + v[5] * n.y + v[6] * n.z + v[7] * d,
+                v[8] * n.x + v[9] * n.y + v[10] * n.z + v[11] * d,
+            ),
+            v[12] * n.x + v[13] * n.y + v[14] * n.z + v[15] * d,
+        );
+        result.normalize();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_points_and_project() {
+        let plane = Plane::from_points(
+            Vec3f32::new(0.0, 2.0, 0.0),
+            Vec3f32::new(0.0, 2.0, 1.0),
+            Vec3f32::new(1.0, 2.0, 0.0),
+        )
+        .unwrap();
+        assert!((0.9999..1.0001).contains(&plane.normal.y));
+        assert!((2.9999..3.0001).contains(&plane.signed_distance(Vec3f32::new(5.0, 5.0, 5.0))));
+
+        let projected = plane.project_point(Vec3f32::new(5.0, -1.0, 3.0));
+        assert!((1.9999..2.0001).contains(&projected.y));
+        assert!((4.9999..5.0001).contains(&projected.x));
+
+        let collinear = Plane::from_points(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 1.0, 1.0),
+            Vec3f32::new(2.0, 2.0, 2.0),
+        );
+        assert!(collinear.is_none());
+
+        // Small triangles are not collinear
+        let small = Plane::from_points(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(0.0003, 0.0, 0.0),
+            Vec3f32::new(0.0, 0.0003, 0.0),
+        )
+        .unwrap();
+        assert!((0.9999..1.0001).contains(&small.normal.z));
+    }
+
+    #[test]
+    fn test_intersect_plane() {
+        // x = 1 and y = 2 intersect in a line along z
+        let a = Plane::new(Vec3f32::new(1.0, 0.0, 0.0), -1.0);
+        let b = Plane::new(Vec3f32::new(0.0, 1.0, 0.0), -2.0);
+        let (point, dir) = a.intersect_plane(&b).unwrap();
+        assert!((0.9999..1.0001).contains(&point.x));
+        assert!((1.9999..2.0001).contains(&point.y));
+        assert!((0.9999..1.0001).contains(&dir.z.abs()));
+        assert!(a.intersect_plane(&a).is_none());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_transformed_with_non_uniform_scale() {
+        // Scale x by 4 and translate by (1, 0, 0)
+        let m = Mat4f32 { values: [
+            4.0, 0.0, 0.0, 1.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]};
+        let mut normal = Vec3f32::new(1.0, 1.0, 0.0);
+        normal.normalize();
+        let point = Vec3f32::new(1.0, 0.0, 0.0);
+        let plane = Plane::from_point_normal(point, normal);
+        let transformed = plane.transformed(&m).unwrap();
+
+        // Transformed points of the plane are on the transformed plane
+        for p in [point, Vec3f32::new(0.0, 1.0, 0.0), Vec3f32::new(2.0, -1.0, 5.0)] {
+            let d = transformed.signed_distance(m.transform_point(p));
+            assert!((-0.0001..0.0001).contains(&d));
+        }
+        assert!((0.9999..1.0001).contains(&transformed.normal.magnitude()));
+    }
+}
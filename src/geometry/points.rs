@@ -0,0 +1,138 @@
+use crate::{
+    geometry::{aabb3f32::Aabb3f32, plane::Plane, sphere::Sphere},
+    mat::mat3f32::Mat3f32,
+    vec::vec3::Vec3f32,
+};
+
+/// Statistics of a point cloud gathered in a single pass
+#[derive(Debug, Clone, Copy)]
+pub struct PointStats {
+    pub centroid: Vec3f32,
+    /// Covariance matrix of the points around the centroid
+    pub covariance: Mat3f32,
+    pub aabb: Aabb3f32,
+}
+
+/// Computes the centroid, covariance and bounding box of the points in one
+/// pass. Returns zeroed statistics at the origin if points is empty.
+pub fn stats(points: &[Vec3f32]) -> PointStats {
+    let origin = Vec3f32::new(0.0, 0.0, 0.0);
+    let Some(&first) = points.first() else {
+        return PointStats {
+            centroid: origin,
+            covariance: Mat3f32::zero(),
+            aabb: Aabb3f32::new(origin, origin),
+        };
+    };
+
+    // Sums are taken relative to the first point, which avoids the
+    // cancellation of the naive one pass formula for clouds far from the origin
+    let mut sum = origin;
+    let mut products = [0.0; 9];
+    let mut aabb = Aabb3f32::new(first, first);
+    for &p in points {
+        aabb.min = aabb.min.min(p);
+        aabb.max = aabb.max.max(p);
+        let d = p - first;
+        sum = sum + d;
+        let d = [d.x, d.y, d.z];
+        for row in 0..3 {
+            for col in 0..3 {
+                products[row * 3 + col] += d[row] * d[col];
+            }
+        }
+    }
+
+    let n = points.len() as f32;
+    let mean = sum / n;
+    let mean = [mean.x, mean.y, mean.z];
+    let covariance = Mat3f32 {
+        values: std::array::from_fn(|i| products[i] / n - mean[i / 3] * mean[i % 3]),
+    };
+    PointStats {
+        centroid: first + sum / n,
+        covariance,
+        aabb,
+    }
+}
+
+/// Returns the average of the points, or the origin if points is empty
+pub fn centroid(points: &[Vec3f32]) -> Vec3f32 {
+    Vec3f32::average(points).unwrap_or(Vec3f32::new(0.0, 0.0, 0.0))
+}
+
+/// Returns the covariance matrix of the points around their centroid
+pub fn covariance(points: &[Vec3f32]) -> Mat3f32 {
+    stats(points).covariance
+}
+
+/// Returns the tight axis aligned bounding box of the points
+pub fn aabb(points: &[Vec3f32]) -> Aabb3f32 {
+    Aabb3f32::from_points(points)
+}
+
+/// Returns the minimal bounding sphere of the points
+pub fn bounding_sphere(points: &[Vec3f32]) -> Sphere {
+    Sphere::from_points(points)
+}
+
+/// Returns the plane minimizing the squared distances to the points. It
+/// goes through the centroid and its normal is the direction of least
+/// variance. Returns None for less than 3 points.
+pub fn best_fit_plane(points: &[Vec3f32]) -> Option<Plane> {
+    if points.len() < 3 {
+        return None;
+    }
+    let stats = stats(points);
+    let (_, basis) = stats.covariance.symmetric_eigen();
+    let normal = basis.rotated_vec(Vec3f32::new(0.0, 0.0, 1.0));
+    Some(Plane::from_point_normal(stats.centroid, normal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let offset = Vec3f32::new(1000.0, -2000.0, 500.0);
+        let points: Vec<Vec3f32> = [
+            Vec3f32::new(-1.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(0.0, -2.0, 0.0),
+            Vec3f32::new(0.0, 2.0, 0.0),
+        ]
+        .iter()
+        .map(|&p| p + offset)
+        .collect();
+
+        let stats = stats(&points);
+        let c = centroid(&points);
+        assert!((999.999..1000.001).contains(&stats.centroid.x));
+        assert!((999.999..1000.001).contains(&c.x));
+        assert!((-2000.001..-1999.999).contains(&c.y));
+        // Variance 0.5 along x, 2 along y and none along z
+        let cov = stats.covariance.values;
+        assert!((0.4999..0.5001).contains(&cov[0]));
+        assert!((1.9999..2.0001).contains(&cov[4]));
+        assert!((-0.0001..0.0001).contains(&cov[8]));
+        assert!((-0.0001..0.0001).contains(&cov[1]));
+        assert!((1000.9999..1001.0001).contains(&stats.aabb.max.x));
+        assert!((-2002.0001..-2001.9999).contains(&stats.aabb.min.y));
+    }
+
+    #[test]
+    fn test_best_fit_plane() {
+        let mut points = Vec::new();
+        for x in -3..=3 {
+            for z in -3..=3 {
+                let noise = if (x + z) % 2 == 0 { 0.01 } else { -0.01 };
+                points.push(Vec3f32::new(x as f32, 2.0 + noise, z as f32));
+            }
+        }
+        let plane = best_fit_plane(&points).unwrap();
+        assert!((0.9999..1.0001).contains(&plane.normal.y.abs()));
+        assert!((-0.001..0.001).contains(&plane.signed_distance(Vec3f32::new(5.0, 2.0, 1.0))));
+        assert!(best_fit_plane(&points[..2]).is_none());
+    }
+}
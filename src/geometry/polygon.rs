@@ -0,0 +1,167 @@
+use crate::vec::vec3::Vec3f32;
+
+/// Returns the normal of the polygon times twice its area using Newell's
+/// method, which sums over all edges and so is robust for concave polygons,
+/// collinear vertices and slightly non-planar faces
+fn newell(vertices: &[Vec3f32]) -> Vec3f32 {
+    let mut n = Vec3f32::new(0.0, 0.0, 0.0);
+    for (i, &a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        n.x += (a.y - b.y) * (a.z + b.z);
+        n.y += (a.z - b.z) * (a.x + b.x);
+        n.z += (a.x - b.x) * (a.y + b.y);
+    }
+    n
+}
+
+/// Returns the normalized normal of the polygon, pointing to the side from
+/// which the vertices appear counter-clockwise. Returns None if the polygon
+/// has no area.
+pub fn normal(vertices: &[Vec3f32]) -> Option<Vec3f32> {
+    let n = newell(vertices);
+    let mag = n.magnitude();
+    // Relative to the size of the polygon, so small polygons still work
+    let extent: f32 = (0..vertices.len())
+        .map(|i| (vertices[(i + 1) % vertices.len()] - vertices[i]).magnitude_squared())
+        .sum();
+    if mag <= f32::EPSILON * extent {
+        return None;
+    }
+    Some(n / mag)
+}
+
+/// Returns the area of the (planar) polygon
+pub fn area(vertices: &[Vec3f32]) -> f32 {
+    newell(vertices).magnitude() / 2.0
+}
+
+/// Returns the center of mass of the polygon area, which differs from the
+/// average of the vertices for uneven vertex distributions.
+/// Returns None if the polygon has no area.
+pub fn centroid(vertices: &[Vec3f32]) -> Option<Vec3f32> {
+    let n = normal(vertices)?;
+    let origin = vertices[0];
+    let mut sum = Vec3f32::new(0.0, 0.0, 0.0);
+    let mut total = 0.0;
+    // Fan of triangles from the first vertex, the signed areas make this
+    // work for concave polygons too
+    for pair in vertices[1..].windows(2) {
+        let (b, c) = (pair[0], pair[1]);
+        let signed_area = Vec3f32::triple(n, b - origin, c - origin);
+        sum = sum + (origin + b + c) * (signed_area / 3.0);
+        total += signed_area;
+    }
+    Some(sum / total)
+}
+
+/// Triangulates a simple planar polygon (concave polygons included, holes
+/// not) by ear clipping. Returns the triangles as indices into vertices,
+/// with the same winding as the polygon. Degenerate polygons still produce
+/// vertices.len() - 2 triangles, some of them possibly flipped or empty.
+pub fn triangulate(vertices: &[Vec3f32]) -> Vec<[usize; 3]> {
+    if vertices.len() < 3 {
+        return Vec::new();
+    }
+    let n = newell(vertices);
+    let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+    let mut triangles = Vec::with_capacity(vertices.len() - 2);
+
+    let convex = |a: Vec3f32, b: Vec3f32, c: Vec3f32| Vec3f32::triple(n, b - a, c - b) > 0.0;
+    let contains = |a: Vec3f32, b: Vec3f32, c: Vec3f32, p: Vec3f32| {
+        Vec3f32::triple(n, b - a, p - a) >= 0.0
+            && Vec3f32::triple(n, c - b, p - b) >= 0.0
+            && Vec3f32::triple(n, a - c, p - c) >= 0.0
+    };
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let ear = (0..count).find(|&i| {
+            let [ia, ib, ic] = [(i + count - 1) % count, i, (i + 1) % count].map(|j| remaining[j]);
+            let (a, b, c) = (vertices[ia], vertices[ib], vertices[ic]);
+            convex(a, b, c)
+                && remaining
+                    .iter()
+                    .all(|&j| j == ia || j == ib || j == ic || !contains(a, b, c, vertices[j]))
+        });
+        // Without an ear the rest is degenerate, clip anything to finish
+        let i = ear.unwrap_or(0);
+        triangles.push([
+            remaining[(i + count - 1) % count],
+            remaining[i],
+            remaining[(i + 1) % count],
+        ]);
+        remaining.remove(i);
+    }
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::triangle::Triangle;
+
+    /// L-shaped polygon in the xz plane, counter-clockwise seen from +y
+    fn l_shape() -> Vec<Vec3f32> {
+        [
+            (0.0, 0.0),
+            (0.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 0.0),
+        ]
+        .iter()
+        .map(|&(x, z)| Vec3f32::new(x, 3.0, z))
+        .collect()
+    }
+
+    #[test]
+    fn test_normal_area_centroid() {
+        let polygon = l_shape();
+        let n = normal(&polygon).unwrap();
+        assert!((0.9999..1.0001).contains(&n.y));
+        assert!((2.9999..3.0001).contains(&area(&polygon)));
+
+        // Average of the centroids of the three unit squares
+        let c = centroid(&polygon).unwrap();
+        let expected = Vec3f32::new(5.0 / 6.0, 3.0, 5.0 / 6.0);
+        assert!((-0.0001..0.0001).contains(&c.distance(expected)));
+
+        let line = [
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(2.0, 0.0, 0.0),
+        ];
+        assert!(normal(&line).is_none());
+        assert!(centroid(&line).is_none());
+
+        // A 0.3 mm triangle
+        let small = [
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(0.0003, 0.0, 0.0),
+            Vec3f32::new(0.0, 0.0003, 0.0),
+        ];
+        assert!((0.9999..1.0001).contains(&normal(&small).unwrap().z));
+        let c = centroid(&small).unwrap();
+        assert!((0.000099..0.000101).contains(&c.x));
+    }
+
+    #[test]
+    fn test_triangulate() {
+        let polygon = l_shape();
+        let n = normal(&polygon).unwrap();
+        let triangles = triangulate(&polygon);
+        assert_eq!(triangles.len(), 4);
+
+        let mut total = 0.0;
+        for [a, b, c] in triangles {
+            let triangle = Triangle::new(polygon[a], polygon[b], polygon[c]);
+            // Same winding as the polygon, so no triangle covers the notch
+            assert!(triangle.normal().dot(n) > 0.0);
+            total += triangle.area();
+        }
+        assert!((2.9999..3.0001).contains(&total));
+        assert!(triangulate(&polygon[..2]).is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::vec::{vec2::Vec2f32, vec3::Vec3f32};
+
+/// Orientation of three points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+    /// The points are (almost) collinear
+    Degenerate,
+}
+
+impl Winding {
+    fn from_signed_area(area: f32, epsilon: f32) -> Self {
+        if area > epsilon {
+            Winding::CounterClockwise
+        } else if area < -epsilon {
+            Winding::Clockwise
+        } else {
+            Winding::Degenerate
+        }
+    }
+}
+
+/// Returns the winding of the 2D triangle a, b, c (with y pointing up).
+/// Triangles with a doubled signed area within epsilon are degenerate.
+pub fn winding_2d(a: Vec2f32, b: Vec2f32, c: Vec2f32, epsilon: f32) -> Winding {
+    Winding::from_signed_area((b - a).cross(c - a), epsilon)
+}
+
+/// Returns the winding of the 3D triangle a, b, c seen from the side the
+/// normal points to. Triangles with a doubled area along the normal within
+/// epsilon are degenerate.
+pub fn winding_3d(a: Vec3f32, b: Vec3f32, c: Vec3f32, normal: Vec3f32, epsilon: f32) -> Winding {
+    Winding::from_signed_area(Vec3f32::triple(normal, b - a, c - a), epsilon)
+}
+
+/// Returns true if all points are within epsilon of a common plane.
+/// Up to three points are always coplanar, so are (almost) collinear sets.
+pub fn are_coplanar(points: &[Vec3f32], epsilon: f32) -> bool {
+    if points.len() < 4 {
+        return true;
+    }
+
+    // Span the plane with the first point, the point farthest from it and
+    // the point farthest from the line through both
+    let a = points[0];
+    let Some(&b) = points.iter().max_by(|p, q| {
+        (**p - a)
+            .magnitude_squared()
+            .total_cmp(&(**q - a).magnitude_squared())
+    }) else {
+        return true;
+    };
+    let ab = b - a;
+    let Some(normal) = points
+        .iter()
+        .map(|&p| ab.cross(p - a))
+        .max_by(|n, m| n.magnitude_squared().total_cmp(&m.magnitude_squared()))
+    else {
+        return true;
+    };
+    let len = normal.magnitude();
+    if len <= f32::EPSILON {
+        return true;
+    }
+    let normal = normal / len;
+    points.iter().all(|&p| normal.dot(p - a).abs() <= epsilon)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winding() {
+        let a = Vec2f32::new(0.0, 0.0);
+        let b = Vec2f32::new(1.0, 0.0);
+        let c = Vec2f32::new(0.0, 1.0);
+        assert_eq!(winding_2d(a, b, c, 1e-6), Winding::CounterClockwise);
+        assert_eq!(winding_2d(a, c, b, 1e-6), Winding::Clockwise);
+        assert_eq!(winding_2d(a, b, b * 2.0, 1e-6), Winding::Degenerate);
+
+        let a = Vec3f32::new(0.0, 0.0, 0.0);
+        let b = Vec3f32::new(1.0, 0.0, 0.0);
+        let c = Vec3f32::new(0.0, 0.0, -1.0);
+        let up = Vec3f32::new(0.0, 1.0, 0.0);
+        assert_eq!(winding_3d(a, b, c, up, 1e-6), Winding::CounterClockwise);
+        assert_eq!(winding_3d(a, b, c, -up, 1e-6), Winding::Clockwise);
+    }
+
+    #[test]
+    fn test_are_coplanar() {
+        let mut points = vec![
+            Vec3f32::new(0.0, 1.0, 0.0),
+            Vec3f32::new(3.0, 1.0, 0.0),
+            Vec3f32::new(1.0, 1.0, 2.0),
+            Vec3f32::new(-4.0, 1.0, 5.0),
+        ];
+        assert!(are_coplanar(&points, 1e-4));
+        points.push(Vec3f32::new(1.0, 1.01, 1.0));
+        assert!(!are_coplanar(&points, 1e-4));
+        assert!(are_coplanar(&points, 0.1));
+        assert!(are_coplanar(&points[..3], 0.0));
+    }
+}
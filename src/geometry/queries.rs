@@ -0,0 +1,217 @@
+use crate::{
+    geometry::{aabb3f32::Aabb3f32, plane::Plane},
+    math::saturate,
+    vec::vec3::Vec3f32,
+};
+
+/// Returns the point on the segment ab closest to p
+pub fn closest_point_on_segment(p: Vec3f32, a: Vec3f32, b: Vec3f32) -> Vec3f32 {
+    let ab = b - a;
+    let len_sqrd = ab.magnitude_squared();
+    if len_sqrd == 0.0 {
+        return a;
+    }
+    let t = saturate((p - a).dot(ab) / len_sqrd);
+    a + ab * t
+}
+
+/// Returns the point on (or inside) the triangle abc closest to p
+pub fn closest_point_on_triangle(p: Vec3f32, a: Vec3f32, b: Vec3f32, c: Vec3f32) -> Vec3f32 {
+    // Checks the voronoi regions of the vertices, edges and the face
+    // (Ericson, Real-Time Collision Detection 5.1.5)
+    let ab = b - a;
+    let ac = c - a;
+
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Returns the point on (or inside) the box closest to p
+pub fn closest_point_on_aabb(p: Vec3f32, aabb: &Aabb3f32) -> Vec3f32 {
+    p.max(aabb.min).min(aabb.max)
+}
+
+/// Returns the point on the plane closest to p.
+/// Make sure the plane normal is normalized.
+pub fn closest_point_on_plane(p: Vec3f32, plane: &Plane) -> Vec3f32 {
+    plane.project_point(p)
+}
+
+/// Returns the distance between p and the segment ab
+pub fn distance_point_segment(p: Vec3f32, a: Vec3f32, b: Vec3f32) -> f32 {
+    p.distance(closest_point_on_segment(p, a, b))
+}
+
+/// Returns the closest points between the segments p1q1 and p2q2, the first
+/// point is on p1q1 and the second on p2q2. If the segments are parallel,
+/// one of the closest pairs is returned.
+pub fn closest_points_segment_segment(
+    p1: Vec3f32,
+    q1: Vec3f32,
+    p2: Vec3f32,
+    q2: Vec3f32,
+) -> (Vec3f32, Vec3f32) {
+    // Ericson, Real-Time Collision Detection 5.1.9
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.magnitude_squared();
+    let e = d2.magnitude_squared();
+    let f = d2.dot(r);
+
+    // Degenerate segments (points)
+    if a <= f32::EPSILON && e <= f32::EPSILON {
+        return (p1, p2);
+    }
+    if a <= f32::EPSILON {
+        return (p1, p2 + d2 * saturate(f / e));
+    }
+    let c = d1.dot(r);
+    if e <= f32::EPSILON {
+        return (p1 + d1 * saturate(-c / a), p2);
+    }
+
+    let b = d1.dot(d2);
+    let denom = a * e - b * b;
+    // For parallel segments (denom = 0) any s works, pick 0
+    let mut s = if denom != 0.0 {
+        saturate((b * f - c * e) / denom)
+    } else {
+        0.0
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = saturate(-c / a);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = saturate((b - c) / a);
+    }
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// Returns the distance between the segments p1q1 and p2q2
+pub fn distance_segment_segment(p1: Vec3f32, q1: Vec3f32, p2: Vec3f32, q2: Vec3f32) -> f32 {
+    let (a, b) = closest_points_segment_segment(p1, q1, p2, q2);
+    a.distance(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_vec_equal(a: Vec3f32, b: Vec3f32) -> bool {
+        (a - b).magnitude() < 0.0001
+    }
+
+    #[test]
+    fn test_closest_point_on_segment() {
+        let a = Vec3f32::new(0.0, 0.0, 0.0);
+        let b = Vec3f32::new(2.0, 0.0, 0.0);
+        let p = closest_point_on_segment(Vec3f32::new(1.0, 3.0, 0.0), a, b);
+        assert!(check_vec_equal(p, Vec3f32::new(1.0, 0.0, 0.0)));
+        let p = closest_point_on_segment(Vec3f32::new(-1.0, 3.0, 0.0), a, b);
+        assert!(check_vec_equal(p, a));
+        let dist = distance_point_segment(Vec3f32::new(5.0, 4.0, 0.0), a, b);
+        assert!((4.9999..5.0001).contains(&dist));
+    }
+
+    #[test]
+    fn test_closest_point_on_triangle() {
+        let a = Vec3f32::new(0.0, 0.0, 0.0);
+        let b = Vec3f32::new(2.0, 0.0, 0.0);
+        let c = Vec3f32::new(0.0, 2.0, 0.0);
+
+        // Face region
+        let p = closest_point_on_triangle(Vec3f32::new(0.5, 0.5, 3.0), a, b, c);
+        assert!(check_vec_equal(p, Vec3f32::new(0.5, 0.5, 0.0)));
+        // Vertex regions
+        let p = closest_point_on_triangle(Vec3f32::new(-1.0, -1.0, 0.0), a, b, c);
+        assert!(check_vec_equal(p, a));
+        let p = closest_point_on_triangle(Vec3f32::new(3.0, -1.0, 0.0), a, b, c);
+        assert!(check_vec_equal(p, b));
+        // Edge regions
+        let p = closest_point_on_triangle(Vec3f32::new(1.0, -1.0, 1.0), a, b, c);
+        assert!(check_vec_equal(p, Vec3f32::new(1.0, 0.0, 0.0)));
+        let p = closest_point_on_triangle(Vec3f32::new(2.0, 2.0, 0.0), a, b, c);
+        assert!(check_vec_equal(p, Vec3f32::new(1.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_closest_point_on_aabb_and_plane() {
+        let aabb = Aabb3f32::new(Vec3f32::new(-1.0, -1.0, -1.0), Vec3f32::new(1.0, 1.0, 1.0));
+        let p = closest_point_on_aabb(Vec3f32::new(3.0, 0.5, -2.0), &aabb);
+        assert!(check_vec_equal(p, Vec3f32::new(1.0, 0.5, -1.0)));
+
+        let plane = Plane::new(Vec3f32::new(0.0, 0.0, 1.0), -1.0);
+        let p = closest_point_on_plane(Vec3f32::new(3.0, 2.0, 5.0), &plane);
+        assert!(check_vec_equal(p, Vec3f32::new(3.0, 2.0, 1.0)));
+    }
+
+    #[test]
+    fn test_segment_segment() {
+        // Crossing segments with a gap of 1 in z
+        let (a, b) = closest_points_segment_segment(
+            Vec3f32::new(-1.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(0.5, -1.0, 1.0),
+            Vec3f32::new(0.5, 1.0, 1.0),
+        );
+        assert!(check_vec_equal(a, Vec3f32::new(0.5, 0.0, 0.0)));
+        assert!(check_vec_equal(b, Vec3f32::new(0.5, 0.0, 1.0)));
+
+        // Parallel segments
+        let dist = distance_segment_segment(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(0.5, 2.0, 0.0),
+            Vec3f32::new(3.0, 2.0, 0.0),
+        );
+        assert!((1.9999..2.0001).contains(&dist));
+
+        // Segments apart along their direction
+        let dist = distance_segment_segment(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(4.0, 0.0, 0.0),
+            Vec3f32::new(5.0, 0.0, 0.0),
+        );
+        assert!((2.9999..3.0001).contains(&dist));
+    }
+}
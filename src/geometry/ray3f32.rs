@@ -0,0 +1,401 @@
+use crate::{
+    float,
+    geometry::{aabb3f32::Aabb3f32, plane::Plane, sphere::Sphere},
+    math::rcp,
+    vec::vec3::Vec3f32,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ray3f32 {
+    pub origin: Vec3f32,
+    /// Distances along the ray are measured in multiples of dir,
+    /// normalize it to get real distances
+    pub dir: Vec3f32,
+}
+
+/// The result of a successful ray intersection
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    /// The hit point is origin + dir * t
+    pub t: f32,
+    pub point: Vec3f32,
+    /// Normalized surface normal at the hit point
+    pub normal: Vec3f32,
+}
+
+/// The result of a successful ray triangle intersection
+#[derive(Debug, Clone, Copy)]
+pub struct TriangleHit {
+    /// The hit point is origin + dir * t
+    pub t: f32,
+    /// Barycentric coordinates, the hit point is also a * (1 - u - v) + b * u + c * v
+    pub u: f32,
+    pub v: f32,
+}
+
+impl TriangleHit {
+    /// Returns the barycentric coordinates (weights of a, b and c) of the hit
+    pub fn barycentric(&self) -> [f32; 3] {
+        [1.0 - self.u - self.v, self.u, self.v]
+    }
+}
+
+impl Ray3f32 {
+    pub fn new(origin: Vec3f32, dir: Vec3f32) -> Self {
+        Self { origin, dir }
+    }
+
+    /// Returns the point origin + dir * t
+    pub fn at(&self, t: f32) -> Vec3f32 {
+        self.origin + self.dir * t
+    }
+
+    /// Returns the hit with the plane if the ray is not parallel to it and
+    /// the plane is not behind the origin. The normal is the plane normal.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<RayHit> {
+        let denom = plane.normal.dot(self.dir);
+        if denom.abs() < f32::EPSILON {
+            return None;
+        }
+        let t = -plane.signed_distance(self.origin) / denom;
+        if t < 0.0 {
+            return None;
+        }
+        Some(RayHit {
+            t,
+            point: self.at(t),
+            normal: plane.normal,
+        })
+    }
+
+    /// Returns the first hit with the surface of the sphere in front of the
+    /// origin. If the origin is inside the sphere, this is where the ray exits.
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<RayHit> {
+        let offset = self.origin - sphere.center;
+        let a = self.dir.magnitude_squared();
+        let half_b = offset.dot(self.dir);
+        let c = offset.magnitude_squared() - sphere.radius * sphere.radius;
+
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 || a == 0.0 {
+            return None;
+        }
+        let sqrt_discriminant = float::sqrt(discriminant);
+        let mut t = (-half_b - sqrt_discriminant) / a;
+        if t < 0.0 {
+            t = (-half_b + sqrt_discriminant) / a;
+            if t < 0.0 {
+                return None;
+            }
+        }
+
+        let point = self.at(t);
+        Some(RayHit {
+            t,
+            point,
+            normal: (point - sphere.center) / sphere.radius,
+        })
+    }
+
+    /// Returns the first hit with the surface of the box in front of the
+    /// origin (slab method). If the origin is inside the box, this is where
+    /// the ray exits.
+    pub fn intersect_aabb(&self, aabb: &Aabb3f32) -> Option<RayHit> {
+        let origin = [self.origin.x, self.origin.y, self.origin.z];
+        let dir = [self.dir.x, self.dir.y, self.dir.z];
+        let min = [aabb.min.x, aabb.min.y, aabb.min.z];
+        let max = [aabb.max.x, aabb.max.y, aabb.max.z];
+
+        let mut t_near = f32::NEG_INFINITY;
+        let mut t_far = f32::INFINITY;
+        // Axis and sign of the slabs the ray enters and exits the box through
+        let mut near_face = (0, 0.0);
+        let mut far_face = (0, 0.0);
+
+        for axis in 0..3 {
+            if dir[axis] == 0.0 {
+                if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                    return None;
+                }
+                continue;
+            }
+
+            let inv_dir = rcp(dir[axis]);
+            let mut t0 = (min[axis] - origin[axis]) * inv_dir;
+            let mut t1 = (max[axis] - origin[axis]) * inv_dir;
+            // The ray enters through the min side when travelling in positive direction
+            let mut sign = -1.0;
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+                sign = 1.0;
+            }
+            if t0 > t_near {
+                t_near = t0;
+                near_face = (axis, sign);
+            }
+            if t1 < t_far {
+                t_far = t1;
+                far_face = (axis, -sign);
+            }
+            if t_near > t_far {
+                return None;
+            }
+        }
+
+        let (t, (axis, sign)) = if t_near >= 0.0 {
+            (t_near, near_face)
+        } else if t_far >= 0.0 {
+            (t_far, far_face)
+        } else {
+            return None;
+        };
+        let mut normal = [0.0; 3];
+        normal[axis] = sign;
+        Some(RayHit {
+            t,
+            point: self.at(t),
+            normal: Vec3f32::new(normal[0], normal[1], normal[2]),
+        })
+    }
+
+    /// Returns the hit with the triangle abc (Möller–Trumbore), both sides
+    /// of the triangle are hit. Fast, but rays through shared edges or
+    /// vertices of a mesh may slip through, see intersect_triangle_watertight.
+    pub fn intersect_triangle(&self, a: Vec3f32, b: Vec3f32, c: Vec3f32) -> Option<TriangleHit> {
+        let ab = b - a;
+        let ac = c - a;
+        let p = self.dir.cross(ac);
+        let det = ab.dot(p);
+        // Relative to the input lengths, so small triangles still hit
+        if det.abs() <= f32::EPSILON * self.dir.magnitude() * ab.magnitude() * ac.magnitude() {
+            return None;
+        }
+        let inv_det = rcp(det);
+
+        let offset = self.origin - a;
+        let u = offset.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(ab);
+        let v = self.dir.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = ac.dot(q) * inv_det;
+        if t < 0.0 {
+            return None;
+        }
+        Some(TriangleHit { t, u, v })
+    }
+
+    /// Returns the hit with the triangle abc, both sides of the triangle are
+    /// hit. Uses the watertight algorithm by Woop, Benthin and Wald: rays
+    /// through edges or vertices shared by triangles always hit at least one
+    /// of them, which makes it the right choice for mesh picking.
+    pub fn intersect_triangle_watertight(
+        &self,
+        a: Vec3f32,
+        b: Vec3f32,
+        c: Vec3f32,
+    ) -> Option<TriangleHit> {
+        let dir = [self.dir.x, self.dir.y, self.dir.z];
+
+        // Permute the axes so that z is the dominant direction axis,
+        // swapping x and y keeps the winding of the triangle
+        let kz = (0..3)
+            .max_by(|&i, &j| dir[i].abs().total_cmp(&dir[j].abs()))
+            .unwrap();
+        let mut kx = (kz + 1) % 3;
+        let mut ky = (kx + 1) % 3;
+        if dir[kz] < 0.0 {
+            std::mem::swap(&mut kx, &mut ky);
+        }
+        if dir[kz] == 0.0 {
+            return None;
+        }
+
+        // Shear so the ray points along +z
+        let sx = dir[kx] / dir[kz];
+        let sy = dir[ky] / dir[kz];
+        let sz = rcp(dir[kz]);
+
+        let relative = |p: Vec3f32| {
+            let p = p - self.origin;
+            let p = [p.x, p.y, p.z];
+            (p[kx] - sx * p[kz], p[ky] - sy * p[kz], sz * p[kz])
+        };
+        let (ax, ay, az) = relative(a);
+        let (bx, by, bz) = relative(b);
+        let (cx, cy, cz) = relative(c);
+
+        // Scaled barycentric coordinates, recomputed in double precision when
+        // they are exactly 0 (on an edge) so neighbouring triangles agree
+        let mut u = cx * by - cy * bx;
+        let mut v = ax * cy - ay * cx;
+        let mut w = bx * ay - by * ax;
+        if u == 0.0 || v == 0.0 || w == 0.0 {
+            u = (cx as f64 * by as f64 - cy as f64 * bx as f64) as f32;
+            v = (ax as f64 * cy as f64 - ay as f64 * cx as f64) as f32;
+            w = (bx as f64 * ay as f64 - by as f64 * ax as f64) as f32;
+        }
+
+        if (u < 0.0 || v < 0.0 || w < 0.0) && (u > 0.0 || v > 0.0 || w > 0.0) {
+            return None;
+        }
+        let det = u + v + w;
+        if det == 0.0 {
+            return None;
+        }
+
+        let t = (u * az + v * bz + w * cz) / det;
+        if t < 0.0 {
+            return None;
+        }
+        Some(TriangleHit {
+            t,
+            u: v / det,
+            v: w / det,
+        })
+    }
+
+    /// Returns the index and hit of the closest triangle hit by the ray.
+    /// Every three consecutive vertices form a triangle, leftover vertices
+    /// are ignored. Uses intersect_triangle_watertight.
+    pub fn intersect_triangles(&self, vertices: &[Vec3f32]) -> Option<(usize, TriangleHit)> {
+        let mut result: Option<(usize, TriangleHit)> = None;
+        for (i, triangle) in vertices.chunks_exact(3).enumerate() {
+            let Some(hit) =
+                self.intersect_triangle_watertight(triangle[0], triangle[1], triangle[2])
+            else {
+                continue;
+            };
+            if result.is_none_or(|(_, closest)| hit.t < closest.t) {
+                result = Some((i, hit));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect_plane() {
+        let ray = Ray3f32::new(Vec3f32::new(0.0, 5.0, 0.0), Vec3f32::new(0.0, -1.0, 0.0));
+        let plane = Plane::new(Vec3f32::new(0.0, 1.0, 0.0), -1.0);
+        let hit = ray.intersect_plane(&plane).unwrap();
+        assert!((3.9999..4.0001).contains(&hit.t));
+        assert!((0.9999..1.0001).contains(&hit.point.y));
+
+        let away = Ray3f32::new(Vec3f32::new(0.0, 5.0, 0.0), Vec3f32::new(0.0, 1.0, 0.0));
+        assert!(away.intersect_plane(&plane).is_none());
+    }
+
+    #[test]
+    fn test_intersect_sphere() {
+        let sphere = Sphere::new(Vec3f32::new(0.0, 0.0, -5.0), 1.0);
+        let ray = Ray3f32::new(Vec3f32::new(0.0, 0.0, 0.0), Vec3f32::new(0.0, 0.0, -1.0));
+        let hit = ray.intersect_sphere(&sphere).unwrap();
+        assert!((3.9999..4.0001).contains(&hit.t));
+        assert!((0.9999..1.0001).contains(&hit.normal.z));
+
+        // From inside the sphere, the exit point is hit
+        let inside = Ray3f32::new(sphere.center, Vec3f32::new(1.0, 0.0, 0.0));
+        let hit = inside.intersect_sphere(&sphere).unwrap();
+        assert!((0.9999..1.0001).contains(&hit.t));
+
+        let miss = Ray3f32::new(Vec3f32::new(0.0, 2.0, 0.0), Vec3f32::new(0.0, 0.0, -1.0));
+        assert!(miss.intersect_sphere(&sphere).is_none());
+    }
+
+    #[test]
+    fn test_intersect_aabb() {
+        let aabb = Aabb3f32::new(Vec3f32::new(1.0, -1.0, -1.0), Vec3f32::new(3.0, 1.0, 1.0));
+        let ray = Ray3f32::new(Vec3f32::new(0.0, 0.5, 0.0), Vec3f32::new(1.0, 0.0, 0.0));
+        let hit = ray.intersect_aabb(&aabb).unwrap();
+        assert!((0.9999..1.0001).contains(&hit.t));
+        assert_eq!((hit.normal.x, hit.normal.y, hit.normal.z), (-1.0, 0.0, 0.0));
+
+        let inside = Ray3f32::new(Vec3f32::new(2.0, 0.0, 0.0), Vec3f32::new(0.0, 1.0, 0.0));
+        let hit = inside.intersect_aabb(&aabb).unwrap();
+        assert!((0.9999..1.0001).contains(&hit.t));
+        assert_eq!((hit.normal.x, hit.normal.y, hit.normal.z), (0.0, 1.0, 0.0));
+
+        let miss = Ray3f32::new(Vec3f32::new(0.0, 1.5, 0.0), Vec3f32::new(1.0, 0.0, 0.0));
+        assert!(miss.intersect_aabb(&aabb).is_none());
+        let behind = Ray3f32::new(Vec3f32::new(0.0, 0.5, 0.0), Vec3f32::new(-1.0, 0.0, 0.0));
+        assert!(behind.intersect_aabb(&aabb).is_none());
+    }
+
+    fn triangle() -> (Vec3f32, Vec3f32, Vec3f32) {
+        (
+            Vec3f32::new(0.0, 0.0, -2.0),
+            Vec3f32::new(2.0, 0.0, -2.0),
+            Vec3f32::new(0.0, 2.0, -2.0),
+        )
+    }
+
+    #[test]
+    fn test_intersect_triangle() {
+        let (a, b, c) = triangle();
+        let ray = Ray3f32::new(Vec3f32::new(0.5, 1.0, 0.0), Vec3f32::new(0.0, 0.0, -1.0));
+        for hit in [
+            ray.intersect_triangle(a, b, c).unwrap(),
+            ray.intersect_triangle_watertight(a, b, c).unwrap(),
+        ] {
+            assert!((1.9999..2.0001).contains(&hit.t));
+            assert!((0.2499..0.2501).contains(&hit.u));
+            assert!((0.4999..0.5001).contains(&hit.v));
+        }
+
+        let miss = Ray3f32::new(Vec3f32::new(1.5, 1.5, 0.0), Vec3f32::new(0.0, 0.0, -1.0));
+        assert!(miss.intersect_triangle(a, b, c).is_none());
+        assert!(miss.intersect_triangle_watertight(a, b, c).is_none());
+
+        let behind = Ray3f32::new(Vec3f32::new(0.5, 1.0, 0.0), Vec3f32::new(0.0, 0.0, 1.0));
+        assert!(behind.intersect_triangle(a, b, c).is_none());
+        assert!(behind.intersect_triangle_watertight(a, b, c).is_none());
+
+        // Legs of 0.3 mm
+        let (a, b, c) = (a * 0.00015, b * 0.00015, c * 0.00015);
+        let ray = Ray3f32::new(
+            Vec3f32::new(0.000075, 0.00015, 0.0),
+            Vec3f32::new(0.0, 0.0, -1.0),
+        );
+        let hit = ray.intersect_triangle(a, b, c).unwrap();
+        assert!((0.2499..0.2501).contains(&hit.u));
+        assert!((0.4999..0.5001).contains(&hit.v));
+    }
+
+    #[test]
+    fn test_watertight_shared_edge() {
+        // Two triangles sharing the diagonal of a quad, a ray exactly
+        // through the diagonal has to hit at least one of them
+        let quad = [
+            Vec3f32::new(0.0, 0.0, -1.0),
+            Vec3f32::new(1.0, 0.0, -1.0),
+            Vec3f32::new(1.0, 1.0, -1.0),
+            Vec3f32::new(0.0, 1.0, -1.0),
+        ];
+        let vertices = [quad[0], quad[1], quad[2], quad[0], quad[2], quad[3]];
+        for i in 0..=10 {
+            let f = i as f32 / 10.0;
+            let ray = Ray3f32::new(Vec3f32::new(f, f, 0.0), Vec3f32::new(0.0, 0.0, -1.0));
+            assert!(ray.intersect_triangles(&vertices).is_some());
+        }
+    }
+
+    #[test]
+    fn test_intersect_triangles_closest() {
+        let (a, b, c) = triangle();
+        let offset = Vec3f32::new(0.0, 0.0, 1.0);
+        let vertices = [a, b, c, a + offset, b + offset, c + offset];
+        let ray = Ray3f32::new(Vec3f32::new(0.5, 0.5, 0.0), Vec3f32::new(0.0, 0.0, -1.0));
+        let (index, hit) = ray.intersect_triangles(&vertices).unwrap();
+        assert_eq!(index, 1);
+        assert!((0.9999..1.0001).contains(&hit.t));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::vec::vec2::Vec2f32;
+
+/// Axis aligned rectangle, for example a region of the screen in pixels
+#[derive(Debug, Clone, Copy)]
+pub struct Rect2f32 {
+    pub min: Vec2f32,
+    pub max: Vec2f32,
+}
+
+impl Rect2f32 {
+    pub fn new(min: Vec2f32, max: Vec2f32) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_origin_size(origin: Vec2f32, size: Vec2f32) -> Self {
+        Self::new(origin, origin + size)
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn size(&self) -> Vec2f32 {
+        self.max - self.min
+    }
+
+    pub fn center(&self) -> Vec2f32 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Returns true if p is inside or on the border of the rectangle
+    pub fn contains_point(&self, p: Vec2f32) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /// Returns true if other is completely inside of self
+    pub fn contains_rect(&self, other: &Rect2f32) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// Returns the overlapping part of self and other, or None if they
+    /// don't overlap. Touching rectangles return an empty rectangle.
+    pub fn intersection(&self, other: &Rect2f32) -> Option<Self> {
+        let min = Vec2f32::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Vec2f32::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        if min.x > max.x || min.y > max.y {
+            return None;
+        }
+        Some(Self::new(min, max))
+    }
+
+    /// Returns the smallest rectangle that contains both self and other
+    pub fn union(&self, other: &Rect2f32) -> Self {
+        Self::new(
+            Vec2f32::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Vec2f32::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// Returns the largest rectangle with the given aspect ratio
+    /// (width / height) centered inside of self, adding bars at the top and
+    /// bottom (letterbox) or the sides (pillarbox) as needed
+    pub fn fit_aspect_ratio(&self, aspect_ratio: f32) -> Self {
+        let size = self.size();
+        let fitted = if size.x / size.y > aspect_ratio {
+            Vec2f32::new(size.y * aspect_ratio, size.y)
+        } else {
+            Vec2f32::new(size.x, size.x / aspect_ratio)
+        };
+        let min = self.center() - fitted / 2.0;
+        Self::from_origin_size(min, fitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let a = Rect2f32::from_origin_size(Vec2f32::new(0.0, 0.0), Vec2f32::new(4.0, 2.0));
+        let b = Rect2f32::new(Vec2f32::new(3.0, 1.0), Vec2f32::new(5.0, 5.0));
+        assert!(a.contains_point(Vec2f32::new(4.0, 1.0)));
+        assert!(!a.contains_point(Vec2f32::new(4.1, 1.0)));
+
+        let i = a.intersection(&b).unwrap();
+        assert!((0.9999..1.0001).contains(&i.width()));
+        assert!((0.9999..1.0001).contains(&i.height()));
+        let c = Rect2f32::new(Vec2f32::new(5.0, 0.0), Vec2f32::new(6.0, 1.0));
+        assert!(a.intersection(&c).is_none());
+
+        let u = a.union(&b);
+        assert!(u.contains_rect(&a) && u.contains_rect(&b));
+        assert!((4.9999..5.0001).contains(&u.height()));
+    }
+
+    #[test]
+    fn test_fit_aspect_ratio() {
+        let screen =
+            Rect2f32::from_origin_size(Vec2f32::new(0.0, 0.0), Vec2f32::new(1920.0, 1200.0));
+        let letterbox = screen.fit_aspect_ratio(16.0 / 9.0);
+        assert!((1919.99..1920.01).contains(&letterbox.width()));
+        assert!((1079.99..1080.01).contains(&letterbox.height()));
+        assert!((59.99..60.01).contains(&letterbox.min.y));
+
+        let pillarbox = screen.fit_aspect_ratio(1.0);
+        assert!((1199.99..1200.01).contains(&pillarbox.width()));
+        assert!((359.99..360.01).contains(&pillarbox.min.x));
+    }
+}
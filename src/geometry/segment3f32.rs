@@ -0,0 +1,62 @@
+use crate::{geometry::queries, vec::vec3::Vec3f32};
+
+/// A line segment between the points a and b
+#[derive(Debug, Clone, Copy)]
+pub struct Segment3f32 {
+    pub a: Vec3f32,
+    pub b: Vec3f32,
+}
+
+impl Segment3f32 {
+    pub fn new(a: Vec3f32, b: Vec3f32) -> Self {
+        Self { a, b }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.a.distance(self.b)
+    }
+
+    /// Returns the point a + (b - a) * t
+    pub fn at(&self, t: f32) -> Vec3f32 {
+        self.a + (self.b - self.a) * t
+    }
+
+    /// Returns the point on the segment closest to p
+    pub fn closest_point(&self, p: Vec3f32) -> Vec3f32 {
+        queries::closest_point_on_segment(p, self.a, self.b)
+    }
+
+    /// Returns the distance between p and the segment
+    pub fn distance_to_point(&self, p: Vec3f32) -> f32 {
+        queries::distance_point_segment(p, self.a, self.b)
+    }
+
+    /// Returns the closest points between self and other, the first point
+    /// is on self and the second on other
+    pub fn closest_points(&self, other: &Segment3f32) -> (Vec3f32, Vec3f32) {
+        queries::closest_points_segment_segment(self.a, self.b, other.a, other.b)
+    }
+
+    /// Returns the distance between self and other
+    pub fn distance_to_segment(&self, other: &Segment3f32) -> f32 {
+        queries::distance_segment_segment(self.a, self.b, other.a, other.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment() {
+        let segment = Segment3f32::new(Vec3f32::new(0.0, 0.0, 0.0), Vec3f32::new(0.0, 4.0, 0.0));
+        assert!((3.9999..4.0001).contains(&segment.length()));
+        assert!((1.9999..2.0001).contains(&segment.at(0.5).y));
+
+        let p = segment.closest_point(Vec3f32::new(1.0, 5.0, 0.0));
+        assert!((3.9999..4.0001).contains(&p.y));
+
+        let other = Segment3f32::new(Vec3f32::new(3.0, 1.0, 0.0), Vec3f32::new(3.0, 1.0, 5.0));
+        assert!((2.9999..3.0001).contains(&segment.distance_to_segment(&other)));
+    }
+}
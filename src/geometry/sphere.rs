@@ -0,0 +1,294 @@
+use crate::{mat::mat4f32::Mat4f32, vec::vec3::Vec3f32};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sphere {
+    pub center: Vec3f32,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(center: Vec3f32, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Returns the minimal bounding sphere of the points (Welzl's algorithm).
+    /// Returns a sphere with radius 0 at the origin if points is empty.
+    pub fn from_points(points: &[Vec3f32]) -> Self {
+        Self::from_points_welzl(points)
+    }
+
+    /// Returns an approximate bounding sphere of the points (Ritter's algorithm).
+    /// This is fast (two passes) but usually 5-20% larger than the minimal sphere.
+    /// Returns a sphere with radius 0 at the origin if points is empty.
+    pub fn from_points_ritter(points: &[Vec3f32]) -> Self {
+        let Some(&first) = points.first() else {
+            return Self::new(Vec3f32::new(0.0, 0.0, 0.0), 0.0);
+        };
+
+        // Find a (roughly) maximally distant pair of points
+        let y = farthest_from(points, first);
+        let z = farthest_from(points, y);
+        let mut result = Self::new((y + z) / 2.0, y.distance(z) / 2.0);
+
+        // Grow the sphere to include all points outside of it
+        for &p in points {
+            let dist = result.center.distance(p);
+            if dist > result.radius {
+                let new_radius = (result.radius + dist) / 2.0;
+                result.center =
+                    result.center + (p - result.center) * ((new_radius - result.radius) / dist);
+                result.radius = new_radius;
+            }
+        }
+        result
+    }
+
+    /// Returns the minimal bounding sphere of the points (Welzl's algorithm
+    /// with the move-to-front heuristic).
+    /// Returns a sphere with radius 0 at the origin if points is empty.
+    pub fn from_points_welzl(points: &[Vec3f32]) -> Self {
+        if points.is_empty() {
+            return Self::new(Vec3f32::new(0.0, 0.0, 0.0), 0.0);
+        }
+        let mut points = points.to_vec();
+        let mut boundary = Vec::with_capacity(4);
+        let len = points.len();
+        welzl(&mut points, len, &mut boundary)
+    }
+
+    /// Returns true if p is inside or on the surface of the sphere
+    pub fn contains_point(&self, p: Vec3f32) -> bool {
+        (p - self.center).magnitude_squared() <= self.radius * self.radius
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        4.0 * std::f32::consts::PI * self.radius * self.radius
+    }
+
+    pub fn volume(&self) -> f32 {
+        4.0 / 3.0 * std::f32::consts::PI * self.radius * self.radius * self.radius
+    }
+
+    /// Returns the point inside or on the surface of the sphere closest to p
+    pub fn closest_point(&self, p: Vec3f32) -> Vec3f32 {
+        let offset = p - self.center;
+        let dist = offset.magnitude();
+        if dist <= self.radius {
+            return p;
+        }
+        self.center + offset * (self.radius / dist)
+    }
+
+    /// Returns the smallest sphere that contains both self and other
+    pub fn merged(&self, other: &Sphere) -> Self {
+        let mut result = *self;
+        result.merge(other);
+        result
+    }
+
+    /// Grows this sphere so it also contains other
+    pub fn merge(&mut self, other: &Sphere) {
+        let offset = other.center - self.center;
+        let dist = offset.magnitude();
+
+        if dist + other.radius <= self.radius {
+            return;
+        }
+        if dist + self.radius <= other.radius {
+            *self = *other;
+            return;
+        }
+
+        let new_radius = (dist + self.radius + other.radius) / 2.0;
+        self.center = self.center + offset * ((new_radius - self.radius) / dist);
+        self.radius = new_radius;
+    }
+
+    /// Returns the sphere transformed by m. Non-uniform scale is handled
+    /// conservatively by using the largest scale factor of m.
+    pub fn transformed(&self, m: &Mat4f32) -> Self {
+        let mut result = *self;
+        result.transform(m);
+        result
+    }
+
+    /// Transforms the sphere by m (see transformed)
+    pub fn transform(&mut self, m: &Mat4f32) {
+        self.center = m.transform_point(self.center);
+        self.radius *= m.max_scale();
+    }
+
+    /// Like contains_point, but with a small tolerance for floating point error
+    fn contains_approx(&self, p: Vec3f32) -> bool {
+        let tolerance = self.radius.abs() * 1e-5 + 1e-6;
+        self.center.distance(p) <= self.radius + tolerance
+    }
+}
+
+fn farthest_from(points: &[Vec3f32], from: Vec3f32) -> Vec3f32 {
+    let mut result = from;
+    let mut max_dist_sqrd = 0.0;
+    for &p in points {
+        let dist_sqrd = (p - from).magnitude_squared();
+        if dist_sqrd > max_dist_sqrd {
+            max_dist_sqrd = dist_sqrd;
+            result = p;
+        }
+    }
+    result
+}
+
+/// Computes the minimal sphere of points[..end] that has all boundary points on its surface.
+/// The recursion depth is bounded by the boundary size (at most 4).
+fn welzl(points: &mut [Vec3f32], end: usize, boundary: &mut Vec<Vec3f32>) -> Sphere {
+    let mut result = sphere_from_boundary(boundary);
+    if boundary.len() == 4 {
+        return result;
+    }
+
+    for i in 0..end {
+        let p = points[i];
+        if !result.contains_approx(p) {
+            boundary.push(p);
+            result = welzl(points, i, boundary);
+            boundary.pop();
+            // Move to front, points that end up on the boundary tend to do so again
+            points[..=i].rotate_right(1);
+        }
+    }
+    result
+}
+
+/// Returns the smallest sphere with all points on its surface.
+/// An empty boundary yields an "empty" sphere that contains nothing.
+fn sphere_from_boundary(boundary: &[Vec3f32]) -> Sphere {
+    match *boundary {
+        [] => Sphere::new(Vec3f32::new(0.0, 0.0, 0.0), -1.0),
+        [a] => Sphere::new(a, 0.0),
+        [a, b] => Sphere::new((a + b) / 2.0, a.distance(b) / 2.0),
+        [a, b, c] => circumsphere_3(a, b, c),
+        [a, b, c, d] => circumsphere_4(a, b, c, d),
+        _ => unreachable!(),
+    }
+}
+
+fn circumsphere_3(a: Vec3f32, b: Vec3f32, c: Vec3f32) -> Sphere {
+    let ab = b - a;
+    let ac = c - a;
+    let n = ab.cross(ac);
+    let denom = 2.0 * n.magnitude_squared();
+
+    // Collinear points, the sphere through the two farthest points contains the third
+    if denom <= f32::EPSILON * ab.magnitude_squared() * ac.magnitude_squared() {
+        let candidates = [(a, b), (a, c), (b, c)];
+        let (p, q) = candidates
+            .into_iter()
+            .max_by(|(p0, q0), (p1, q1)| p0.distance(*q0).total_cmp(&p1.distance(*q1)))
+            .unwrap();
+        return Sphere::new((p + q) / 2.0, p.distance(q) / 2.0);
+    }
+
+    let offset =
+        (n.cross(ab) * ac.magnitude_squared() + ac.cross(n) * ab.magnitude_squared()) / denom;
+    Sphere::new(a + offset, offset.magnitude())
+}
+
+fn circumsphere_4(a: Vec3f32, b: Vec3f32, c: Vec3f32, d: Vec3f32) -> Sphere {
+    let ab = b - a;
+    let ac = c - a;
+    let ad = d - a;
+    let denom = 2.0 * ab.dot(ac.cross(ad));
+
+    // Coplanar points, use the smallest sphere through three of them that contains the fourth
+    if denom.abs() <= f32::EPSILON * ab.magnitude() * ac.magnitude() * ad.magnitude() {
+        let points = [a, b, c, d];
+        return [(a, b, c), (a, b, d), (a, c, d), (b, c, d)]
+            .into_iter()
+            .map(|(p, q, r)| circumsphere_3(p, q, r))
+            .filter(|s| points.iter().all(|&p| s.contains_approx(p)))
+            .min_by(|s0, s1| s0.radius.total_cmp(&s1.radius))
+            .unwrap_or_else(|| Sphere::from_points_ritter(&points));
+    }
+
+    let offset = (ac.cross(ad) * ab.magnitude_squared()
+        + ad.cross(ab) * ac.magnitude_squared()
+        + ab.cross(ac) * ad.magnitude_squared())
+        / denom;
+    Sphere::new(a + offset, offset.magnitude())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_points() -> Vec<Vec3f32> {
+        vec![
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(-1.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 1.0, 0.0),
+            Vec3f32::new(0.0, -1.0, 0.0),
+            Vec3f32::new(0.0, 0.0, 1.0),
+            Vec3f32::new(0.0, 0.0, -1.0),
+            Vec3f32::new(0.3, 0.2, -0.1),
+            Vec3f32::new(0.5, 0.5, 0.5),
+        ]
+    }
+
+    #[test]
+    fn test_welzl_is_minimal() {
+        let points = test_points();
+        let sphere = Sphere::from_points_welzl(&points);
+        assert!((0.9999..1.0001).contains(&sphere.radius));
+        assert!(sphere.center.magnitude() < 0.0001);
+        assert!(points.iter().all(|&p| sphere.contains_approx(p)));
+    }
+
+    #[test]
+    fn test_ritter_contains_all_points() {
+        let points = test_points();
+        let sphere = Sphere::from_points_ritter(&points);
+        assert!(sphere.radius >= 0.9999);
+        assert!(points.iter().all(|&p| sphere.contains_approx(p)));
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let sphere = Sphere::new(Vec3f32::new(1.0, 0.0, 0.0), 2.0);
+        let p = sphere.closest_point(Vec3f32::new(1.0, 5.0, 0.0));
+        assert!((0.9999..1.0001).contains(&p.x));
+        assert!((1.9999..2.0001).contains(&p.y));
+
+        let inside = Vec3f32::new(1.5, 0.5, 0.0);
+        let p = sphere.closest_point(inside);
+        assert_eq!((p.x, p.y, p.z), (inside.x, inside.y, inside.z));
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = Sphere::new(Vec3f32::new(-2.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(Vec3f32::new(2.0, 0.0, 0.0), 1.0);
+        let merged = a.merged(&b);
+        assert!((2.9999..3.0001).contains(&merged.radius));
+        assert!(merged.center.magnitude() < 0.0001);
+
+        let inner = Sphere::new(Vec3f32::new(0.5, 0.0, 0.0), 0.1);
+        let merged = merged.merged(&inner);
+        assert!((2.9999..3.0001).contains(&merged.radius));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_transformed() {
+        let m = Mat4f32 { values: [
+            2.0, 0.0, 0.0, 1.0,
+            0.0, 3.0, 0.0, 2.0,
+            0.0, 0.0, 1.0, 3.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]};
+        let sphere = Sphere::new(Vec3f32::new(1.0, 1.0, 1.0), 1.0).transformed(&m);
+        assert!((2.9999..3.0001).contains(&sphere.center.x));
+        assert!((4.9999..5.0001).contains(&sphere.center.y));
+        assert!((3.9999..4.0001).contains(&sphere.center.z));
+        assert!((2.9999..3.0001).contains(&sphere.radius));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    mat::mat3f32::Mat3f32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// Returns the TBN matrix with the tangent, bitangent and normal as its
+/// columns. The tangent is orthonormalized against the normal (Gram-Schmidt)
+/// and the bitangent is handedness * normal x tangent, where handedness is
+/// 1 or -1 (the w component of glTF tangents).
+/// Make sure normal is normalized and tangent is not parallel to it.
+pub fn build_tangent_basis(normal: Vec3f32, tangent: Vec3f32, handedness: f32) -> Mat3f32 {
+    let mut t = tangent - normal * normal.dot(tangent);
+    t.normalize();
+    let b = normal.cross(t) * handedness.signum();
+    Mat3f32::from_columns(t, b, normal)
+}
+
+/// Returns the tangent (the direction of increasing u) of a triangle and
+/// the handedness of its UV mapping, 1 if the direction of increasing v is
+/// normal x tangent and -1 if the mapping is mirrored. Per vertex tangents
+/// are usually the normalized sum of the tangents of adjacent triangles.
+/// Returns None if the triangle or its UVs are degenerate.
+pub fn tangent_from_uv(positions: [Vec3f32; 3], uvs: [Vec2f32; 3]) -> Option<(Vec3f32, f32)> {
+    let e1 = positions[1] - positions[0];
+    let e2 = positions[2] - positions[0];
+    let d1 = uvs[1] - uvs[0];
+    let d2 = uvs[2] - uvs[0];
+
+    // The checks are relative to the edge lengths, so they work for
+    // triangles and UV footprints of any size
+    let det = d1.cross(d2);
+    if det.abs() <= f32::EPSILON * d1.magnitude() * d2.magnitude() {
+        return None;
+    }
+    let normal = e1.cross(e2);
+    if normal.magnitude_squared() <= f32::EPSILON * e1.magnitude_squared() * e2.magnitude_squared()
+    {
+        return None;
+    }
+    let mut tangent = e1 * d2.y - e2 * d1.y;
+    let bitangent = (e2 * d1.x - e1 * d2.x) / det;
+    let scale = e1.magnitude_squared() * d2.magnitude_squared()
+        + e2.magnitude_squared() * d1.magnitude_squared();
+    if tangent.magnitude_squared() <= f32::EPSILON * scale {
+        return None;
+    }
+    // Dividing by det only matters for its sign before normalizing
+    tangent = tangent / det;
+    tangent.normalize();
+
+    let handedness = if normal.cross(tangent).dot(bitangent) < 0.0 {
+        -1.0
+    } else {
+        1.0
+    };
+    Some((tangent, handedness))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tangent_basis() {
+        let positions = [
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(2.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 0.0, -2.0),
+        ];
+        let uvs = [
+            Vec2f32::new(0.0, 0.0),
+            Vec2f32::new(1.0, 0.0),
+            Vec2f32::new(0.0, 1.0),
+        ];
+        let (tangent, handedness) = tangent_from_uv(positions, uvs).unwrap();
+        assert!((0.9999..1.0001).contains(&tangent.x));
+        assert!((0.9999..1.0001).contains(&handedness));
+
+        // Mirroring u flips the tangent and the handedness
+        let mirrored = uvs.map(|uv| Vec2f32::new(-uv.x, uv.y));
+        let (tangent, handedness) = tangent_from_uv(positions, mirrored).unwrap();
+        assert!((-1.0001..-0.9999).contains(&tangent.x));
+        assert!((-1.0001..-0.9999).contains(&handedness));
+
+        // A 1 cm triangle with a tiny UV footprint
+        let small = positions.map(|p| p * 0.005);
+        let fine = uvs.map(|uv| uv * (1.0 / 1024.0));
+        let (tangent, handedness) = tangent_from_uv(small, fine).unwrap();
+        assert!((0.9999..1.0001).contains(&tangent.x));
+        assert!((0.9999..1.0001).contains(&handedness));
+        let collinear = [small[0], small[1], small[1] * 2.0];
+        assert!(tangent_from_uv(collinear, fine).is_none());
+
+        let normal = Vec3f32::new(0.0, 1.0, 0.0);
+        let tbn = build_tangent_basis(normal, Vec3f32::new(1.0, 0.5, 0.0), 1.0);
+        let t = tbn.column(0);
+        let b = tbn.column(1);
+        assert!((-0.0001..0.0001).contains(&t.dot(normal)));
+        assert!((0.9999..1.0001).contains(&t.magnitude()));
+        assert!((-1.0001..-0.9999).contains(&b.z));
+        assert!((0.9999..1.0001).contains(&tbn.column(2).y));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::vec::vec3::Vec3f32;
+use std::ops::{Add, Mul};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Triangle {
+    pub a: Vec3f32,
+    pub b: Vec3f32,
+    pub c: Vec3f32,
+}
+
+impl Triangle {
+    pub fn new(a: Vec3f32, b: Vec3f32, c: Vec3f32) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Returns the normalized face normal, pointing to the side from which
+    /// a, b, c appear counter-clockwise.
+    /// Make sure the triangle is not degenerate (zero area).
+    pub fn normal(&self) -> Vec3f32 {
+        let mut normal = (self.b - self.a).cross(self.c - self.a);
+        normal.normalize();
+        normal
+    }
+
+    pub fn area(&self) -> f32 {
+        (self.b - self.a).cross(self.c - self.a).magnitude() / 2.0
+    }
+
+    pub fn centroid(&self) -> Vec3f32 {
+        (self.a + self.b + self.c) / 3.0
+    }
+
+    /// Returns the barycentric coordinates (weights of a, b and c) of the
+    /// projection of p onto the plane of the triangle, or None if the
+    /// triangle is degenerate. p is inside the triangle if all weights are
+    /// in 0..=1.
+    pub fn barycentric(&self, p: Vec3f32) -> Option<[f32; 3]> {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let ap = p - self.a;
+
+        let d00 = ab.dot(ab);
+        let d01 = ab.dot(ac);
+        let d11 = ac.dot(ac);
+        let d20 = ap.dot(ab);
+        let d21 = ap.dot(ac);
+        let denom = d00 * d11 - d01 * d01;
+        if denom.abs() <= f32::EPSILON * d00 * d11 {
+            return None;
+        }
+
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+        Some([1.0 - v - w, v, w])
+    }
+
+    /// Returns the point with the given barycentric coordinates
+    pub fn point_at(&self, barycentric: [f32; 3]) -> Vec3f32 {
+        interpolate(barycentric, [self.a, self.b, self.c])
+    }
+}
+
+/// Interpolates vertex attributes (e.g. positions, normals or UVs) of a
+/// triangle with barycentric coordinates. Interpolated normals have to be
+/// normalized again.
+pub fn interpolate<T>(barycentric: [f32; 3], values: [T; 3]) -> T
+where
+    T: Add<Output = T> + Mul<f32, Output = T> + Copy,
+{
+    values[0] * barycentric[0] + values[1] * barycentric[1] + values[2] * barycentric[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::ray3f32::Ray3f32, vec::vec2::Vec2f32};
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(2.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 2.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_normal_area_centroid() {
+        let triangle = triangle();
+        let normal = triangle.normal();
+        assert!((0.9999..1.0001).contains(&normal.z));
+        assert!((1.9999..2.0001).contains(&triangle.area()));
+        let centroid = triangle.centroid();
+        assert!((0.6666..0.6667).contains(&centroid.x));
+        assert!((0.6666..0.6667).contains(&centroid.y));
+    }
+
+    #[test]
+    fn test_barycentric_round_trip() {
+        let triangle = triangle();
+        let p = Vec3f32::new(0.5, 1.0, 0.0);
+        let barycentric = triangle.barycentric(p).unwrap();
+        assert!((0.2499..0.2501).contains(&barycentric[0]));
+        assert!((0.2499..0.2501).contains(&barycentric[1]));
+        assert!((0.4999..0.5001).contains(&barycentric[2]));
+
+        let q = triangle.point_at(barycentric);
+        assert!((p - q).magnitude() < 0.0001);
+    }
+
+    #[test]
+    fn test_interpolate_uv_of_ray_hit() {
+        let triangle = triangle();
+        let uvs = [
+            Vec2f32::new(0.0, 0.0),
+            Vec2f32::new(1.0, 0.0),
+            Vec2f32::new(0.0, 1.0),
+        ];
+        let ray = Ray3f32::new(Vec3f32::new(1.0, 0.5, 1.0), Vec3f32::new(0.0, 0.0, -1.0));
+        let hit = ray
+            .intersect_triangle(triangle.a, triangle.b, triangle.c)
+            .unwrap();
+        let uv = interpolate(hit.barycentric(), uvs);
+        assert!((0.4999..0.5001).contains(&uv.x));
+        assert!((0.2499..0.2501).contains(&uv.y));
+    }
+}
@@ -1,3 +1,17 @@
+pub mod angle;
+pub mod anim;
+pub mod camera;
+pub mod color;
+pub mod fixed;
+mod float;
+pub mod geometry;
 pub mod mat;
+pub mod math;
+mod ops;
+pub mod render;
 pub mod rotor;
+pub mod spatial;
+pub mod transform;
 pub mod vec;
+#[cfg(feature = "xr")]
+pub mod xr;
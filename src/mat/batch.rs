@@ -0,0 +1,130 @@
+use crate::mat::{colmat4f32::ColMat4f32, mat4f32::Mat4f32};
+
+/// Computes out[i] = sum over k of coeff(i, k) * basis row k for the four
+/// rows i of out, with the same order of operations as the multiplication
+/// of Mat4f32, so the results are bit-identical to it
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn combine(coeff: impl Fn(usize, usize) -> f32, basis: &[f32; 16], out: &mut [f32; 16]) {
+    use std::arch::x86_64::*;
+    // SAFETY: SSE is part of every x86_64 target and all loads and stores
+    // stay within the 16 values of basis and out
+    unsafe {
+        let rows = [0, 4, 8, 12].map(|k| _mm_loadu_ps(basis.as_ptr().add(k)));
+        for i in 0..4 {
+            let mut acc = _mm_mul_ps(_mm_set1_ps(coeff(i, 0)), rows[0]);
+            for (k, &row) in rows.iter().enumerate().skip(1) {
+                acc = _mm_add_ps(acc, _mm_mul_ps(_mm_set1_ps(coeff(i, k)), row));
+            }
+            _mm_storeu_ps(out.as_mut_ptr().add(i * 4), acc);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+#[inline(always)]
+fn combine(coeff: impl Fn(usize, usize) -> f32, basis: &[f32; 16], out: &mut [f32; 16]) {
+    // Written lane by lane so the compiler can vectorize it
+    for i in 0..4 {
+        let mut acc = [0.0; 4];
+        for j in 0..4 {
+            acc[j] = coeff(i, 0) * basis[j];
+        }
+        for k in 1..4 {
+            for j in 0..4 {
+                acc[j] += coeff(i, k) * basis[k * 4 + j];
+            }
+        }
+        out[i * 4..i * 4 + 4].copy_from_slice(&acc);
+    }
+}
+
+/// Computes out[i] = parent * locals[i], for composing the world matrices
+/// of many instances with a shared parent. Gives the same results as the
+/// multiplication of Mat4f32, but runs on SIMD registers where available.
+/// Panics if locals and out have different lengths.
+pub fn mul_batch(parent: &Mat4f32, locals: &[Mat4f32], out: &mut [Mat4f32]) {
+    assert_eq!(
+        locals.len(),
+        out.len(),
+        "mul_batch needs one output per local"
+    );
+    let p = &parent.values;
+    for (local, out) in locals.iter().zip(out) {
+        combine(|i, k| p[i * 4 + k], &local.values, &mut out.values);
+    }
+}
+
+/// Same as mul_batch, but writes the results in column major order, ready
+/// to be copied into an instance buffer. This fuses the transposition into
+/// the multiplication, so it costs nothing extra.
+/// Panics if locals and out have different lengths.
+pub fn mul_batch_column_major(parent: &Mat4f32, locals: &[Mat4f32], out: &mut [ColMat4f32]) {
+    assert_eq!(
+        locals.len(),
+        out.len(),
+        "mul_batch needs one output per local"
+    );
+    // Column j of the product is the sum of the parent columns weighted by
+    // column j of the local matrix
+    let parent_columns = parent.transposed();
+    for (local, out) in locals.iter().zip(out) {
+        let l = &local.values;
+        combine(|j, k| l[k * 4 + j], &parent_columns.values, &mut out.values);
+    }
+}
+
+/// Writes the matrices in column major order into out, for instance buffer
+/// uploads of matrices that are already composed.
+/// Panics if matrices and out have different lengths.
+pub fn write_column_major(matrices: &[Mat4f32], out: &mut [ColMat4f32]) {
+    assert_eq!(
+        matrices.len(),
+        out.len(),
+        "write_column_major needs one output per matrix"
+    );
+    for (m, out) in matrices.iter().zip(out) {
+        *out = ColMat4f32::from(*m);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32};
+
+    #[test]
+    fn test_mul_batch() {
+        let parent = Mat4f32::perspective(1.1, 1.3, 0.1, 50.0)
+            * Mat4f32::view_from_pose(
+                Vec3f32::new(1.0, 2.0, 3.0),
+                &Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), 0.4),
+            );
+        let locals: Vec<Mat4f32> = (0..37)
+            .map(|i| {
+                let angle = i as f32 * 0.37;
+                let axis = Vec3f32::new(angle.cos(), 0.6, angle.sin()).normalized();
+                Mat4f32::from_basis(
+                    Rot3Df32::from_axis_angle(axis, angle).rotated_vec(Vec3f32::new(1.5, 0.0, 0.0)),
+                    Rot3Df32::from_axis_angle(axis, angle).rotated_vec(Vec3f32::new(0.0, 0.5, 0.0)),
+                    Rot3Df32::from_axis_angle(axis, angle).rotated_vec(Vec3f32::new(0.0, 0.0, 2.0)),
+                    Vec3f32::new(i as f32, -3.0, 0.25 * i as f32),
+                )
+            })
+            .collect();
+
+        let mut out = vec![Mat4f32::zero(); locals.len()];
+        mul_batch(&parent, &locals, &mut out);
+        let mut columns = vec![ColMat4f32::zero(); locals.len()];
+        mul_batch_column_major(&parent, &locals, &mut columns);
+        let mut written = vec![ColMat4f32::zero(); locals.len()];
+        write_column_major(&out, &mut written);
+
+        for ((local, result), column) in locals.iter().zip(&out).zip(&columns) {
+            // Bit-identical to the plain multiplication
+            assert_eq!(*result, parent * *local);
+            assert_eq!(*column, ColMat4f32::from(parent * *local));
+        }
+        assert_eq!(written, columns);
+    }
+}
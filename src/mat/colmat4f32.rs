@@ -0,0 +1,200 @@
+use crate::{mat::mat4f32::Mat4f32, ops::forward_mixed_binop, vec::vec3::Vec3f32};
+use serde::{Deserialize, Serialize};
+use std::ops::Mul;
+
+/// 4x4 matrix stored in column major order, the layout GLSL and WGSL
+/// expect. Mirrors the API of Mat4f32 (column vector convention), so it
+/// can be uploaded to the GPU without any conversion.
+///
+/// The column major storage of a matrix is the row major storage of its
+/// transpose. from_transpose and into_transpose use that to reinterpret the
+/// values without moving them around.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColMat4f32 {
+    /// Column major order
+    pub values: [f32; 16],
+}
+
+impl ColMat4f32 {
+    /// Returns a matrix with all values set to 0.0
+    pub fn zero() -> ColMat4f32 {
+        ColMat4f32 { values: [0.0; 16] }
+    }
+
+    /// Returns the identity matrix
+    pub fn identity() -> ColMat4f32 {
+        Self::from_transpose(Mat4f32::identity())
+    }
+
+    /// Returns the transpose of m, by reinterpreting its row major values
+    /// as column major values
+    pub fn from_transpose(m: Mat4f32) -> ColMat4f32 {
+        ColMat4f32 { values: m.values }
+    }
+
+    /// Returns the transpose of self as a row major matrix, by reinterpreting
+    /// the column major values as row major values
+    pub fn into_transpose(self) -> Mat4f32 {
+        Mat4f32 {
+            values: self.values,
+        }
+    }
+
+    /// Returns the transposed matrix
+    pub fn transposed(&self) -> ColMat4f32 {
+        Self::from_transpose(self.into_transpose().transposed())
+    }
+
+    /// Returns the inverse matrix, or None if the matrix is not invertible
+    pub fn inverse(&self) -> Option<ColMat4f32> {
+        // The inverse of the transpose is the transpose of the inverse
+        self.into_transpose().inverse().map(Self::from_transpose)
+    }
+
+    /// See Mat4f32::look_at
+    pub fn look_at(eye: Vec3f32, target: Vec3f32, up: Vec3f32) -> ColMat4f32 {
+        Mat4f32::look_at(eye, target, up).into()
+    }
+
+    /// See Mat4f32::perspective
+    pub fn perspective(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> ColMat4f32 {
+        Mat4f32::perspective(fov_y, aspect_ratio, near, far).into()
+    }
+
+    /// See Mat4f32::orthographic
+    pub fn orthographic(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        near: f32,
+        far: f32,
+    ) -> ColMat4f32 {
+        Mat4f32::orthographic(left, right, bottom, top, near, far).into()
+    }
+
+    /// Returns the columns, same as Mat4f32::get_column_major but without
+    /// reordering any values
+    pub fn get_column_major(&self) -> [[f32; 4]; 4] {
+        let v = &self.values;
+        [
+            [v[0], v[1], v[2], v[3]],
+            [v[4], v[5], v[6], v[7]],
+            [v[8], v[9], v[10], v[11]],
+            [v[12], v[13], v[14], v[15]],
+        ]
+    }
+
+    /// Transforms the point p (w = 1), ignoring the projective row
+    pub fn transform_point(&self, p: Vec3f32) -> Vec3f32 {
+        let v = &self.values;
+        Vec3f32::new(
+            v[0] * p.x + v[4] * p.y + v[8] * p.z + v[12],
+            v[1] * p.x + v[5] * p.y + v[9] * p.z + v[13],
+            v[2] * p.x + v[6] * p.y + v[10] * p.z + v[14],
+        )
+    }
+
+    /// Transforms the point p (w = 1) and divides the result by its w
+    /// component, used for projection matrices
+    pub fn transform_point_projective(&self, p: Vec3f32) -> Vec3f32 {
+        let v = &self.values;
+        let w = v[3] * p.x + v[7] * p.y + v[11] * p.z + v[15];
+        self.transform_point(p) / w
+    }
+
+    /// Transforms the direction d (w = 0), so translation is ignored
+    pub fn transform_vector(&self, d: Vec3f32) -> Vec3f32 {
+        let v = &self.values;
+        Vec3f32::new(
+            v[0] * d.x + v[4] * d.y + v[8] * d.z,
+            v[1] * d.x + v[5] * d.y + v[9] * d.z,
+            v[2] * d.x + v[6] * d.y + v[10] * d.z,
+        )
+    }
+
+    /// Returns the largest scale factor of the upper 3x3 part
+    /// (the length of the longest basis column)
+    pub fn max_scale(&self) -> f32 {
+        Mat4f32::from(*self).max_scale()
+    }
+}
+
+impl Default for ColMat4f32 {
+    /// Returns the identity matrix
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<Mat4f32> for ColMat4f32 {
+    /// Converts the storage order, which transposes the values
+    fn from(m: Mat4f32) -> Self {
+        Self::from_transpose(m.transposed())
+    }
+}
+
+impl From<ColMat4f32> for Mat4f32 {
+    /// Converts the storage order, which transposes the values
+    fn from(m: ColMat4f32) -> Self {
+        m.into_transpose().transposed()
+    }
+}
+
+impl Mul for &ColMat4f32 {
+    type Output = ColMat4f32;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // (a * b)^T = b^T * a^T, and the transposes are free
+        ColMat4f32::from_transpose(rhs.into_transpose() * self.into_transpose())
+    }
+}
+
+impl Mul for ColMat4f32 {
+    type Output = ColMat4f32;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        <&ColMat4f32>::mul(&self, &rhs)
+    }
+}
+
+forward_mixed_binop!(impl Mul, mul for ColMat4f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_mat_equal(a: &Mat4f32, b: &Mat4f32) -> bool {
+        a.values
+            .iter()
+            .zip(b.values.iter())
+            .all(|(a, b)| (a - b).abs() < 0.0001)
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_matches_row_major() {
+        let a = Mat4f32 { values: [
+            1.0, 2.0, 0.0, 4.0,
+            0.0, 1.0, 3.0, -1.0,
+            2.0, 0.0, 1.0, 0.5,
+            0.0, 0.0, 0.0, 1.0,
+        ]};
+        let b = Mat4f32::perspective(1.0, 1.5, 0.1, 10.0);
+        let (col_a, col_b) = (ColMat4f32::from(a), ColMat4f32::from(b));
+        assert_eq!(col_a.values[1], 0.0);
+        assert_eq!(col_a.values[4], 2.0);
+        assert_eq!(col_a.get_column_major(), a.get_column_major());
+
+        assert!(check_mat_equal(&(col_a * col_b).into(), &(a * b)));
+        assert!(check_mat_equal(&col_a.inverse().unwrap().into(), &a.inverse().unwrap()));
+        assert!(check_mat_equal(&col_a.transposed().into(), &a.transposed()));
+        assert_eq!(Mat4f32::from(col_a), a);
+
+        let p = Vec3f32::new(0.5, -2.0, -3.0);
+        assert!((-0.0001..0.0001).contains(&col_b.transform_point_projective(p).distance(b.transform_point_projective(p))));
+        assert!((-0.0001..0.0001).contains(&col_a.transform_vector(p).distance(a.transform_vector(p))));
+        assert!((-0.0001..0.0001).contains(&(col_a.max_scale() - a.max_scale())));
+        assert_eq!(ColMat4f32::default(), ColMat4f32::identity());
+    }
+}
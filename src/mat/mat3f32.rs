@@ -0,0 +1,266 @@
+use crate::{float, ops::forward_mixed_binop, rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32};
+use std::ops::Mul;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Mat3f32 {
+    /// Row major order
+    pub values: [f32; 9],
+}
+
+impl Mat3f32 {
+    /// Returns a matrix with all values set to 0.0
+    pub fn zero() -> Mat3f32 {
+        Mat3f32 { values: [0.0; 9] }
+    }
+
+    /// Returns the identity matrix
+    #[rustfmt::skip]
+    pub fn identity() -> Mat3f32 {
+        Mat3f32 { values: [
+            1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0,
+            0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns the matrix with the vectors x, y and z as its columns
+    #[rustfmt::skip]
+    pub fn from_columns(x: Vec3f32, y: Vec3f32, z: Vec3f32) -> Mat3f32 {
+        Mat3f32 { values: [
+            x.x, y.x, z.x,
+            x.y, y.y, z.y,
+            x.z, y.z, z.z,
+        ]}
+    }
+
+    /// Returns the column with the given index (0..3)
+    pub fn column(&self, index: usize) -> Vec3f32 {
+        Vec3f32::new(
+            self.values[index],
+            self.values[3 + index],
+            self.values[6 + index],
+        )
+    }
+
+    pub fn get_column_major(&self) -> [[f32; 3]; 3] {
+        [
+            [self.values[0], self.values[3], self.values[6]],
+            [self.values[1], self.values[4], self.values[7]],
+            [self.values[2], self.values[5], self.values[8]],
+        ]
+    }
+
+    /// Returns the resulting vector of the multiplication self * v
+    pub fn mul_vec(&self, v: Vec3f32) -> Vec3f32 {
+        let m = &self.values;
+        Vec3f32::new(
+            m[0] * v.x + m[1] * v.y + m[2] * v.z,
+            m[3] * v.x + m[4] * v.y + m[5] * v.z,
+            m[6] * v.x + m[7] * v.y + m[8] * v.z,
+        )
+    }
+
+    /// Returns the inverse matrix or None if the matrix is not invertible
+    pub fn inverse(&self) -> Option<Mat3f32> {
+        let m = &self.values;
+        let r0 = Vec3f32::new(m[0], m[1], m[2]);
+        let r1 = Vec3f32::new(m[3], m[4], m[5]);
+        let r2 = Vec3f32::new(m[6], m[7], m[8]);
+
+        // The columns of the inverse are the cross products of the rows
+        let c0 = r1.cross(r2);
+        let det = r0.dot(c0);
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        Some(Mat3f32::from_columns(
+            c0 * inv_det,
+            r2.cross(r0) * inv_det,
+            r0.cross(r1) * inv_det,
+        ))
+    }
+
+    /// Computes eigenvalues and eigenvectors of a symmetric matrix with the
+    /// cyclic Jacobi method. The eigenvectors are the columns of the returned
+    /// matrix, they are orthonormal but not sorted.
+    pub(crate) fn jacobi_eigen(&self) -> ([f32; 3], Mat3f32) {
+        let mut a = [
+            [self.values[0], self.values[1], self.values[2]],
+            [self.values[3], self.values[4], self.values[5]],
+            [self.values[6], self.values[7], self.values[8]],
+        ];
+        let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+        for _ in 0..32 {
+            let off_diagonal = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+            if off_diagonal < 1e-20 {
+                break;
+            }
+
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q].abs() < 1e-20 {
+                    continue;
+                }
+
+                // Rotation angle that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + float::sqrt(theta * theta + 1.0));
+                let c = 1.0 / float::sqrt(t * t + 1.0);
+                let s = t * c;
+
+                // a = J^T * a * J
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                // v = v * J
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+
+        (
+            [a[0][0], a[1][1], a[2][2]],
+            Mat3f32 {
+                values: std::array::from_fn(|i| v[i / 3][i % 3]),
+            },
+        )
+    }
+
+    /// Computes the eigendecomposition of a symmetric matrix. The eigenvalues
+    /// are sorted from largest to smallest and the rotor rotates the x, y and
+    /// z axes onto the corresponding eigenvectors, so self equals
+    /// R * diag(eigenvalues) * R^T. Make sure the matrix is symmetric.
+    pub fn symmetric_eigen(&self) -> ([f32; 3], Rot3Df32) {
+        let (values, vectors) = self.jacobi_eigen();
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| values[b].total_cmp(&values[a]));
+
+        let x_axis = vectors.column(order[0]);
+        let y_axis = vectors.column(order[1]);
+        // Enforce a right-handed basis so it can be expressed as a rotor
+        let z_axis = x_axis.cross(y_axis);
+        (
+            order.map(|i| values[i]),
+            Rot3Df32::from_basis(x_axis, y_axis, z_axis),
+        )
+    }
+}
+
+impl Mul for Mat3f32 {
+    type Output = Mat3f32;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut values = [0.0; 9];
+        for row in 0..3 {
+            for col in 0..3 {
+                values[row * 3 + col] = self.values[row * 3] * rhs.values[col]
+                    + self.values[row * 3 + 1] * rhs.values[3 + col]
+                    + self.values[row * 3 + 2] * rhs.values[6 + col];
+            }
+        }
+        Mat3f32 { values }
+    }
+}
+
+impl Mul for &Mat3f32 {
+    type Output = Mat3f32;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        *self * *rhs
+    }
+}
+
+forward_mixed_binop!(impl Mul, mul for Mat3f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::approx_eq;
+
+    fn check_f32_equal(a: f32, b: f32) -> bool {
+        approx_eq(a, b, 0.0001)
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn mat_multiplication() {
+        let left = Mat3f32 { values: [
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+            7.0, 8.0, 9.0,
+        ]};
+        let right = Mat3f32 { values: [
+            9.0, 8.0, 7.0,
+            6.0, 5.0, 4.0,
+            3.0, 2.0, 1.0,
+        ]};
+        let correct_result = [
+            30.0, 24.0, 18.0,
+            84.0, 69.0, 54.0,
+            138.0, 114.0, 90.0,
+        ];
+        let result = left * right;
+        for (value, correct) in result.values.into_iter().zip(correct_result) {
+            assert!(check_f32_equal(value, correct));
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_jacobi_eigen() {
+        let m = Mat3f32 { values: [
+            2.0, 1.0, 0.0,
+            1.0, 2.0, 0.0,
+            0.0, 0.0, 5.0,
+        ]};
+        let (values, vectors) = m.jacobi_eigen();
+        let mut sorted = values;
+        sorted.sort_by(f32::total_cmp);
+        assert!(check_f32_equal(sorted[0], 1.0));
+        assert!(check_f32_equal(sorted[1], 3.0));
+        assert!(check_f32_equal(sorted[2], 5.0));
+
+        for (i, value) in values.into_iter().enumerate() {
+            let v = vectors.column(i);
+            let mv = m.mul_vec(v);
+            assert!(check_f32_equal(mv.x, v.x * value));
+            assert!(check_f32_equal(mv.y, v.y * value));
+            assert!(check_f32_equal(mv.z, v.z * value));
+            assert!(check_f32_equal(v.magnitude(), 1.0));
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_symmetric_eigen() {
+        let m = Mat3f32 { values: [
+            4.0, 1.0, 0.5,
+            1.0, 3.0, 0.0,
+            0.5, 0.0, 1.0,
+        ]};
+        let (values, rotor) = m.symmetric_eigen();
+        assert!(values[0] >= values[1] && values[1] >= values[2]);
+
+        let axes = [
+            Vec3f32::new(1.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 1.0, 0.0),
+            Vec3f32::new(0.0, 0.0, 1.0),
+        ];
+        for (axis, value) in axes.into_iter().zip(values) {
+            let v = rotor.rotated_vec(axis);
+            let mv = m.mul_vec(v);
+            assert!(check_f32_equal(mv.x, v.x * value));
+            assert!(check_f32_equal(mv.y, v.y * value));
+            assert!(check_f32_equal(mv.z, v.z * value));
+        }
+    }
+}
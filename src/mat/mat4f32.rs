@@ -1,5 +1,11 @@
+use crate::{
+    float, geometry::plane::Plane, math, ops::forward_mixed_binop, rotor::rot3df32::Rot3Df32,
+    transform::transform_error::TransformError, vec::vec3::Vec3f32,
+};
+use serde::{Deserialize, Serialize};
 use std::ops::Mul;
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Mat4f32 {
     /// Row major order
     pub values: [f32; 16],
@@ -22,6 +28,175 @@ impl Mat4f32 {
         ]}
     }
 
+    /// Returns the transposed matrix
+    pub fn transposed(&self) -> Mat4f32 {
+        let mut values = [0.0; 16];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = self.values[(i % 4) * 4 + i / 4];
+        }
+        Mat4f32 { values }
+    }
+
+    /// Returns the inverse matrix or None if the matrix is not invertible
+    #[rustfmt::skip]
+    pub fn inverse(&self) -> Option<Mat4f32> {
+        let [
+            a00, a01, a02, a03,
+            a10, a11, a12, a13,
+            a20, a21, a22, a23,
+            a30, a31, a32, a33,
+        ] = self.values;
+
+        // 2x2 sub-determinants of the upper and lower two rows
+        let s0 = a00 * a11 - a01 * a10;
+        let s1 = a00 * a12 - a02 * a10;
+        let s2 = a00 * a13 - a03 * a10;
+        let s3 = a01 * a12 - a02 * a11;
+        let s4 = a01 * a13 - a03 * a11;
+        let s5 = a02 * a13 - a03 * a12;
+        let c0 = a20 * a31 - a21 * a30;
+        let c1 = a20 * a32 - a22 * a30;
+        let c2 = a20 * a33 - a23 * a30;
+        let c3 = a21 * a32 - a22 * a31;
+        let c4 = a21 * a33 - a23 * a31;
+        let c5 = a22 * a33 - a23 * a32;
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let mut values = [
+            a11 * c5 - a12 * c4 + a13 * c3,
+            -a01 * c5 + a02 * c4 - a03 * c3,
+            a31 * s5 - a32 * s4 + a33 * s3,
+            -a21 * s5 + a22 * s4 - a23 * s3,
+
+            -a10 * c5 + a12 * c2 - a13 * c1,
+            a00 * c5 - a02 * c2 + a03 * c1,
+            -a30 * s5 + a32 * s2 - a33 * s1,
+            a20 * s5 - a22 * s2 + a23 * s1,
+
+            a10 * c4 - a11 * c2 + a13 * c0,
+            -a00 * c4 + a01 * c2 - a03 * c0,
+            a30 * s4 - a31 * s2 + a33 * s0,
+            -a20 * s4 + a21 * s2 - a23 * s0,
+
+            -a10 * c3 + a11 * c1 - a12 * c0,
+            a00 * c3 - a01 * c1 + a02 * c0,
+            -a30 * s3 + a31 * s1 - a32 * s0,
+            a20 * s3 - a21 * s1 + a22 * s0,
+        ];
+        for value in values.iter_mut() {
+            *value *= inv_det;
+        }
+        Some(Mat4f32 { values })
+    }
+
+    /// Returns a right-handed view matrix for a camera at eye looking at
+    /// target (the camera looks down its -z axis).
+    /// Make sure up is not parallel to the view direction.
+    #[rustfmt::skip]
+    pub fn look_at(eye: Vec3f32, target: Vec3f32, up: Vec3f32) -> Mat4f32 {
+        let mut f = target - eye;
+        f.normalize();
+        let mut r = f.cross(up);
+        r.normalize();
+        let u = r.cross(f);
+        Mat4f32 { values: [
+            r.x, r.y, r.z, -r.dot(eye),
+            u.x, u.y, u.z, -u.dot(eye),
+            -f.x, -f.y, -f.z, f.dot(eye),
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns the view matrix of a camera at position with the given
+    /// orientation, the inverse of its world transform. The camera looks
+    /// down its local -z axis with local +y up, like look_at.
+    #[rustfmt::skip]
+    pub fn view_from_pose(position: Vec3f32, rotation: &Rot3Df32) -> Mat4f32 {
+        let x = rotation.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0));
+        let y = rotation.rotated_vec(Vec3f32::new(0.0, 1.0, 0.0));
+        let z = rotation.rotated_vec(Vec3f32::new(0.0, 0.0, 1.0));
+        Mat4f32 { values: [
+            x.x, x.y, x.z, -x.dot(position),
+            y.x, y.y, y.z, -y.dot(position),
+            z.x, z.y, z.z, -z.dot(position),
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns a right-handed perspective projection with the vertical field
+    /// of view fov_y (in radians) onto clip space with depth 0..1.
+    /// See render::conventions::Conventions for other backends.
+    #[rustfmt::skip]
+    pub fn perspective(fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> Mat4f32 {
+        let f = 1.0 / float::tan(fov_y / 2.0);
+        Mat4f32 { values: [
+            f / aspect_ratio, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, far / (near - far), near * far / (near - far),
+            0.0, 0.0, -1.0, 0.0,
+        ]}
+    }
+
+    /// Returns a right-handed orthographic projection of the view space box
+    /// left..right, bottom..top and -near..-far onto clip space with depth 0..1.
+    /// See render::conventions::Conventions for other backends.
+    #[rustfmt::skip]
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4f32 {
+        let (w, h, d) = (right - left, top - bottom, far - near);
+        Mat4f32 { values: [
+            2.0 / w, 0.0, 0.0, -(right + left) / w,
+            0.0, 2.0 / h, 0.0, -(top + bottom) / h,
+            0.0, 0.0, -1.0 / d, -near / d,
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns the matrix that maps the unit axes to x_axis, y_axis and
+    /// z_axis and the origin to origin. The axes don't have to be
+    /// normalized or orthogonal, so this can express any coordinate frame.
+    #[rustfmt::skip]
+    pub fn from_basis(x_axis: Vec3f32, y_axis: Vec3f32, z_axis: Vec3f32, origin: Vec3f32) -> Mat4f32 {
+        let (x, y, z, o) = (x_axis, y_axis, z_axis, origin);
+        Mat4f32 { values: [
+            x.x, y.x, z.x, o.x,
+            x.y, y.y, z.y, o.y,
+            x.z, y.z, z.z, o.z,
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns a shear matrix. Each factor is named after the coordinate
+    /// it changes, followed by the coordinate it is multiplied with, for
+    /// example x_y shifts x by x_y * y.
+    #[rustfmt::skip]
+    pub fn from_shear(x_y: f32, x_z: f32, y_x: f32, y_z: f32, z_x: f32, z_y: f32) -> Mat4f32 {
+        Mat4f32 { values: [
+            1.0, x_y, x_z, 0.0,
+            y_x, 1.0, y_z, 0.0,
+            z_x, z_y, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns the matrix that mirrors points across the plane, for
+    /// planar reflections. It flips the triangle winding.
+    /// Make sure the plane normal is normalized.
+    #[rustfmt::skip]
+    pub fn reflection(plane: &Plane) -> Mat4f32 {
+        let Plane { normal: n, d } = *plane;
+        Mat4f32 { values: [
+            1.0 - 2.0 * n.x * n.x, -2.0 * n.x * n.y, -2.0 * n.x * n.z, -2.0 * n.x * d,
+            -2.0 * n.y * n.x, 1.0 - 2.0 * n.y * n.y, -2.0 * n.y * n.z, -2.0 * n.y * d,
+            -2.0 * n.z * n.x, -2.0 * n.z * n.y, 1.0 - 2.0 * n.z * n.z, -2.0 * n.z * d,
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
     pub fn get_column_major(&self) -> [[f32; 4]; 4] {
         [
             [
@@ -50,6 +225,110 @@ impl Mat4f32 {
             ],
         ]
     }
+
+    /// Transforms the point p (w = 1), ignoring the projective row
+    pub fn transform_point(&self, p: Vec3f32) -> Vec3f32 {
+        let v = &self.values;
+        Vec3f32::new(
+            v[0] * p.x + v[1] * p.y + v[2] * p.z + v[3],
+            v[4] * p.x + v[5] * p.y + v[6] * p.z + v[7],
+            v[8] * p.x + v[9] * p.y + v[10] * p.z + v[11],
+        )
+    }
+
+    /// Transforms the point p (w = 1) and divides the result by its w
+    /// component, used for projection matrices
+    pub fn transform_point_projective(&self, p: Vec3f32) -> Vec3f32 {
+        let v = &self.values;
+        let w = v[12] * p.x + v[13] * p.y + v[14] * p.z + v[15];
+        self.transform_point(p) / w
+    }
+
+    /// Transforms the direction d (w = 0), so translation is ignored
+    pub fn transform_vector(&self, d: Vec3f32) -> Vec3f32 {
+        let v = &self.values;
+        Vec3f32::new(
+            v[0] * d.x + v[1] * d.y + v[2] * d.z,
+            v[4] * d.x + v[5] * d.y + v[6] * d.z,
+            v[8] * d.x + v[9] * d.y + v[10] * d.z,
+        )
+    }
+
+    /// Returns the largest scale factor of the upper 3x3 part
+    /// (the length of the longest basis column)
+    pub fn max_scale(&self) -> f32 {
+        let v = &self.values;
+        let x = v[0] * v[0] + v[4] * v[4] + v[8] * v[8];
+        let y = v[1] * v[1] + v[5] * v[5] + v[9] * v[9];
+        let z = v[2] * v[2] + v[6] * v[6] + v[10] * v[10];
+        float::sqrt(x.max(y).max(z))
+    }
+
+    /// Returns the columns of the upper 3x3 part, the transformed x, y
+    /// and z axes
+    fn basis(&self) -> [Vec3f32; 3] {
+        let v = &self.values;
+        [
+            Vec3f32::new(v[0], v[4], v[8]),
+            Vec3f32::new(v[1], v[5], v[9]),
+            Vec3f32::new(v[2], v[6], v[10]),
+        ]
+    }
+
+    /// Returns true if the last row is exactly 0, 0, 0, 1, so the matrix
+    /// maps points to points without a projective divide
+    pub fn is_affine(&self) -> bool {
+        self.values[12..] == [0.0, 0.0, 0.0, 1.0]
+    }
+
+    /// Returns true if the upper 3x3 part only rotates (or mirrors): the
+    /// axes have length 1 and are perpendicular, both within eps
+    pub fn is_orthonormal(&self, eps: f32) -> bool {
+        let [x, y, z] = self.basis();
+        [x, y, z]
+            .iter()
+            .all(|a| math::approx_eq(a.magnitude(), 1.0, eps))
+            && math::approx_eq(x.dot(y), 0.0, eps)
+            && math::approx_eq(y.dot(z), 0.0, eps)
+            && math::approx_eq(z.dot(x), 0.0, eps)
+    }
+
+    /// Returns true if the upper 3x3 part is a rotation times a scale that
+    /// is the same along every axis (no shear), so normals can be
+    /// transformed with it directly. eps is relative to the scale.
+    pub fn has_uniform_scale(&self, eps: f32) -> bool {
+        let [x, y, z] = self.basis();
+        let scale = self.max_scale();
+        if scale == 0.0 {
+            return false;
+        }
+        Mat4f32::from_basis(x / scale, y / scale, z / scale, Vec3f32::new(0.0, 0.0, 0.0))
+            .is_orthonormal(eps)
+    }
+
+    /// Checks that the matrix is usable as an object transform: finite,
+    /// affine and invertible
+    pub fn validate(&self) -> Result<(), TransformError> {
+        if !self.values.iter().all(|v| v.is_finite()) {
+            return Err(TransformError::NonFinite);
+        }
+        if !self.is_affine() {
+            return Err(TransformError::NotAffine);
+        }
+        let [x, y, z] = self.basis();
+        let scale = self.max_scale();
+        if Vec3f32::triple(x, y, z).abs() <= f32::EPSILON * scale * scale * scale {
+            return Err(TransformError::Singular);
+        }
+        Ok(())
+    }
+}
+
+impl Default for Mat4f32 {
+    /// Returns the identity matrix
+    fn default() -> Self {
+        Self::identity()
+    }
 }
 
 impl Mul for &Mat4f32 {
@@ -119,13 +398,16 @@ impl Mul for Mat4f32 {
     type Output = Mat4f32;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        &self * &rhs
+        <&Mat4f32>::mul(&self, &rhs)
     }
 }
 
+forward_mixed_binop!(impl Mul, mul for Mat4f32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::approx_eq;
 
     // Approximates equality, should work for the small
     // numbers in the unit tests
@@ -139,7 +421,96 @@ mod tests {
     }
 
     fn check_f32_equal(a: f32, b: f32) -> bool {
-        (a - b).abs() < 0.0001
+        approx_eq(a, b, 0.0001)
+    }
+
+    #[test]
+    fn test_default() {
+        let m = Mat4f32::default();
+        assert_eq!(m, Mat4f32::identity());
+        assert_ne!(m, Mat4f32::zero());
+    }
+
+    #[test]
+    fn test_view_from_pose() {
+        let eye = Vec3f32::new(1.0, 2.0, 3.0);
+        let target = Vec3f32::new(-2.0, 0.5, 1.0);
+        let up = Vec3f32::new(0.0, 1.0, 0.0);
+        let look_at = Mat4f32::look_at(eye, target, up);
+
+        let mut forward = target - eye;
+        forward.normalize();
+        let mut right = forward.cross(up);
+        right.normalize();
+        let rotation = Rot3Df32::from_basis(right, right.cross(forward), -forward);
+        let view = Mat4f32::view_from_pose(eye, &rotation);
+        assert!(check_mat_equal(&view, &look_at));
+
+        let world = Mat4f32::from_basis(right, right.cross(forward), -forward, eye);
+        assert!(check_mat_equal(&(view * world), &Mat4f32::identity()));
+    }
+
+    #[test]
+    fn test_basis_and_shear() {
+        let m = Mat4f32::from_basis(
+            Vec3f32::new(2.0, 0.0, 0.0),
+            Vec3f32::new(1.0, 1.0, 0.0),
+            Vec3f32::new(0.0, 0.0, -1.0),
+            Vec3f32::new(5.0, 6.0, 7.0),
+        );
+        let p = m.transform_point(Vec3f32::new(1.0, 2.0, 3.0));
+        assert!(check_f32_equal(p.x, 9.0));
+        assert!(check_f32_equal(p.y, 8.0));
+        assert!(check_f32_equal(p.z, 4.0));
+
+        let shear = Mat4f32::from_shear(0.5, 0.0, 0.0, 0.0, 0.0, -1.0);
+        let p = shear.transform_point(Vec3f32::new(1.0, 2.0, 3.0));
+        assert!(check_f32_equal(p.x, 2.0));
+        assert!(check_f32_equal(p.y, 2.0));
+        assert!(check_f32_equal(p.z, 1.0));
+    }
+
+    #[test]
+    fn test_reflection() {
+        let plane = Plane::new(Vec3f32::new(0.0, 1.0, 0.0), -2.0);
+        let m = Mat4f32::reflection(&plane);
+        let p = m.transform_point(Vec3f32::new(1.0, 5.0, -3.0));
+        assert!(check_f32_equal(p.x, 1.0));
+        assert!(check_f32_equal(p.y, -1.0));
+        assert!(check_f32_equal(p.z, -3.0));
+        assert!(check_mat_equal(&(m * m), &Mat4f32::identity()));
+    }
+
+    #[test]
+    fn test_view_and_projection() {
+        let eye = Vec3f32::new(1.0, 2.0, 3.0);
+        let view = Mat4f32::look_at(
+            eye,
+            Vec3f32::new(1.0, 2.0, -7.0),
+            Vec3f32::new(0.0, 1.0, 0.0),
+        );
+        let p = view.transform_point(Vec3f32::new(2.0, 3.0, 0.0));
+        assert!(check_f32_equal(p.x, 1.0));
+        assert!(check_f32_equal(p.y, 1.0));
+        assert!(check_f32_equal(p.z, -3.0));
+
+        let proj = Mat4f32::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 5.0);
+        let near = proj.transform_point(Vec3f32::new(2.0, -1.0, -1.0));
+        assert!(check_f32_equal(near.x, 1.0));
+        assert!(check_f32_equal(near.y, -1.0));
+        assert!(check_f32_equal(near.z, 0.0));
+        assert!(check_f32_equal(
+            proj.transform_point(Vec3f32::new(0.0, 0.0, -5.0)).z,
+            1.0
+        ));
+
+        let proj = Mat4f32::perspective(std::f32::consts::FRAC_PI_2, 2.0, 1.0, 10.0);
+        let corner = proj.transform_point_projective(Vec3f32::new(20.0, 10.0, -10.0));
+        assert!(check_f32_equal(corner.x, 1.0));
+        assert!(check_f32_equal(corner.y, 1.0));
+        assert!(check_f32_equal(corner.z, 1.0));
+        let near = proj.transform_point_projective(Vec3f32::new(0.0, 0.0, -1.0));
+        assert!(check_f32_equal(near.z, 0.0));
     }
 
     #[test]
@@ -172,4 +543,56 @@ mod tests {
 
         assert!(check_mat_equal(&(left * right), &correct_result));
     }
+
+    #[test]
+    #[rustfmt::skip]
+    fn mat_inverse() {
+        let m = Mat4f32 {
+            values: [
+                2.0, 0.0, 0.0, 1.0,
+                0.0, 0.0, -3.0, 2.0,
+                0.0, 1.0, 0.0, 3.0,
+                0.0, 0.0, 0.0, 1.0,
+            ],
+        };
+        let inverse = m.inverse().unwrap();
+        assert!(check_mat_equal(&(m * inverse), &Mat4f32::identity()));
+        assert!(check_mat_equal(&(inverse * m), &Mat4f32::identity()));
+        assert!(Mat4f32::zero().inverse().is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let rotation = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), 0.7).rotation_mat();
+        assert!(rotation.is_affine() && rotation.is_orthonormal(0.0001));
+        assert_eq!(rotation.validate(), Ok(()));
+
+        let scaled = Mat4f32::from_basis(
+            Vec3f32::new(2.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 0.0, 2.0),
+            Vec3f32::new(0.0, -2.0, 0.0),
+            Vec3f32::new(1.0, 2.0, 3.0),
+        );
+        assert!(scaled.has_uniform_scale(0.0001) && !scaled.is_orthonormal(0.0001));
+        let sheared = Mat4f32::from_shear(0.5, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert!(!sheared.has_uniform_scale(0.0001));
+        assert_eq!(sheared.validate(), Ok(()));
+
+        let proj = Mat4f32::perspective(1.0, 1.0, 0.1, 10.0);
+        assert_eq!(proj.validate(), Err(TransformError::NotAffine));
+        let mut flat = Mat4f32::identity();
+        flat.values[10] = 0.0;
+        assert_eq!(flat.validate(), Err(TransformError::Singular));
+        flat.values[3] = f32::NAN;
+        assert_eq!(flat.validate(), Err(TransformError::NonFinite));
+    }
+
+    #[test]
+    fn mat_transposed() {
+        let mut m = Mat4f32::zero();
+        m.values[1] = 1.0;
+        let transposed = m.transposed();
+        assert_eq!(transposed.values[4], 1.0);
+        assert_eq!(transposed.values[1], 0.0);
+    }
 }
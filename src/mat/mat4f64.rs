@@ -1,3 +1,4 @@
+use crate::ops::forward_mixed_binop;
 use std::ops::Mul;
 
 pub struct Mat4f64 {
@@ -123,6 +124,8 @@ impl Mul for Mat4f64 {
     }
 }
 
+forward_mixed_binop!(impl Mul, mul for Mat4f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,2 +1,5 @@
+pub mod batch;
+pub mod colmat4f32;
+pub mod mat3f32;
 pub mod mat4f32;
 pub mod mat4f64;
@@ -0,0 +1,79 @@
+// Scalar helpers named after their shader counterparts (HLSL saturate and
+// rcp, GLSL inversesqrt), for porting shader code
+
+use crate::float;
+
+/// Clamps x to 0..=1
+#[inline]
+pub fn saturate(x: f32) -> f32 {
+    x.clamp(0.0, 1.0)
+}
+
+/// Returns the reciprocal 1 / x
+#[inline]
+pub fn rcp(x: f32) -> f32 {
+    1.0 / x
+}
+
+/// Returns an approximation of 1 / sqrt(x) for positive x, the bit trick
+/// followed by one Newton step. The relative error is below 0.18%, which is
+/// fine for normalizing shading vectors but not for geometry. On current
+/// CPUs 1.0 / x.sqrt() is usually just as fast, prefer it where the
+/// accuracy matters.
+#[inline]
+pub fn fast_rsqrt(x: f32) -> f32 {
+    let y = f32::from_bits(0x5f37_59df - (x.to_bits() >> 1));
+    y * (1.5 - 0.5 * x * y * y)
+}
+
+/// Returns acos(x) with x clamped to -1..=1, so dot products of normalized
+/// vectors that are slightly too large because of rounding don't give NaN
+#[inline]
+pub fn safe_acos(x: f32) -> f32 {
+    float::acos(x.clamp(-1.0, 1.0))
+}
+
+/// Returns 1.0 or -1.0 with the sign of x. Unlike signum, -0.0 gives -1.0,
+/// which keeps the sign bit of values that were rounded to zero.
+#[inline]
+pub fn sign_not_zero(x: f32) -> f32 {
+    1.0_f32.copysign(x)
+}
+
+/// Returns |x| to the power of e with the sign of x, for curves that
+/// should be mirrored at 0 (gamepad response curves, signed gamma)
+#[inline]
+pub fn signed_pow(x: f32, e: f32) -> f32 {
+    float::powf(x.abs(), e).copysign(x)
+}
+
+/// Returns true if a and b differ by at most eps
+#[inline]
+pub fn approx_eq(a: f32, b: f32, eps: f32) -> bool {
+    (a - b).abs() <= eps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_math() {
+        assert_eq!(saturate(-0.5), 0.0);
+        assert_eq!(saturate(0.25), 0.25);
+        assert_eq!(saturate(3.0), 1.0);
+        assert_eq!(rcp(4.0), 0.25);
+        for x in [0.001_f32, 0.5, 1.0, 2.0, 77.0, 1e6] {
+            let exact = 1.0 / x.sqrt();
+            assert!(approx_eq(fast_rsqrt(x), exact, exact * 0.0018));
+        }
+        assert!(approx_eq(safe_acos(1.0000001), 0.0, 0.0001));
+        assert!(approx_eq(safe_acos(-1.5), PI, 0.0001));
+        assert_eq!(sign_not_zero(-0.0), -1.0);
+        assert_eq!(sign_not_zero(0.0), 1.0);
+        assert!(approx_eq(signed_pow(-2.0, 3.0), -8.0, 0.0001));
+        assert!(approx_eq(signed_pow(4.0, 0.5), 2.0, 0.0001));
+        assert!(!approx_eq(1.0, 1.1, 0.01));
+    }
+}
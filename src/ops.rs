@@ -0,0 +1,103 @@
+// Operator impls for references, so that a * b, &a * b, a * &b and &a * &b
+// all work like they do for the primitive types. Hot loops over large
+// types (matrices) can then borrow instead of copying.
+
+/// forward_ref_binop!(impl Add, add for A, B) implements &A + B, A + &B and
+/// &A + &B for Copy types using the impl of A + B
+macro_rules! forward_ref_binop {
+    (impl $imp:ident, $method:ident for $t:ty, $u:ty) => {
+        impl std::ops::$imp<$u> for &$t {
+            type Output = <$t as std::ops::$imp<$u>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: $u) -> Self::Output {
+                std::ops::$imp::$method(*self, rhs)
+            }
+        }
+
+        impl std::ops::$imp<&$u> for $t {
+            type Output = <$t as std::ops::$imp<$u>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: &$u) -> Self::Output {
+                std::ops::$imp::$method(self, *rhs)
+            }
+        }
+
+        impl std::ops::$imp<&$u> for &$t {
+            type Output = <$t as std::ops::$imp<$u>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: &$u) -> Self::Output {
+                std::ops::$imp::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+/// forward_mixed_binop!(impl Mul, mul for A) implements A * &A and &A * A
+/// using the impl of &A * &A, for types that are too large to copy around
+macro_rules! forward_mixed_binop {
+    (impl $imp:ident, $method:ident for $t:ty) => {
+        impl std::ops::$imp<&$t> for $t {
+            type Output = <&'static $t as std::ops::$imp<&'static $t>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: &$t) -> Self::Output {
+                std::ops::$imp::$method(&self, rhs)
+            }
+        }
+
+        impl std::ops::$imp<$t> for &$t {
+            type Output = <&'static $t as std::ops::$imp<&'static $t>>::Output;
+
+            #[inline]
+            fn $method(self, rhs: $t) -> Self::Output {
+                std::ops::$imp::$method(self, &rhs)
+            }
+        }
+    };
+}
+
+/// forward_ref_unop!(impl Neg, neg for A) implements -&A for Copy types
+/// using the impl of -A
+macro_rules! forward_ref_unop {
+    (impl $imp:ident, $method:ident for $t:ty) => {
+        impl std::ops::$imp for &$t {
+            type Output = <$t as std::ops::$imp>::Output;
+
+            #[inline]
+            fn $method(self) -> Self::Output {
+                std::ops::$imp::$method(*self)
+            }
+        }
+    };
+}
+
+pub(crate) use forward_mixed_binop;
+pub(crate) use forward_ref_binop;
+pub(crate) use forward_ref_unop;
+
+#[cfg(test)]
+mod tests {
+    use crate::{mat::mat4f32::Mat4f32, vec::vec3::Vec3f32};
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_reference_forms() {
+        let (a, b) = (Vec3f32::new(1.0, 2.0, 3.0), Vec3f32::new(-4.0, 0.5, 2.0));
+        let sum = a + b;
+        for v in [&a + b, a + &b, &a + &b] {
+            assert!((-0.0001..0.0001).contains(&v.distance(sum)));
+        }
+        assert!((-0.0001..0.0001).contains(&(&a * &2.0).distance(a * 2.0)));
+        assert!((-0.0001..0.0001).contains(&(-&a).distance(-a)));
+
+        let m = Mat4f32::from_shear(0.5, 0.0, 1.0, 0.0, 0.0, 2.0);
+        let n = Mat4f32::reflection(&crate::geometry::plane::Plane::new(a / a.magnitude(), 1.0));
+        let product = m * n;
+        assert_eq!(&m * n, product);
+        assert_eq!(m * &n, product);
+        assert_eq!(&m * &n, product);
+    }
+}
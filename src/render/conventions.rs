@@ -0,0 +1,388 @@
+use crate::{
+    float,
+    geometry::ray3f32::Ray3f32,
+    mat::mat4f32::Mat4f32,
+    render::{cubemap, depth::DepthConvention, viewport::Viewport},
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+use std::f32::consts::FRAC_PI_2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    /// The camera looks down -z in view space
+    Right,
+    /// The camera looks down +z in view space
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpAxis {
+    Y,
+    Z,
+}
+
+/// Which way +y in normalized device coordinates points on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NdcY {
+    Up,
+    Down,
+}
+
+/// Where the first row of a render target ends up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureOrigin {
+    TopLeft,
+    BottomLeft,
+}
+
+/// The coordinate system conventions of a graphics backend. Build the view
+/// and projection matrices through one of the presets, so switching the
+/// backend only means switching the preset.
+/// The free functions and the Mat4f32 constructors use Conventions::WGPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conventions {
+    pub handedness: Handedness,
+    pub up_axis: UpAxis,
+    pub depth: DepthConvention,
+    pub ndc_y: NdcY,
+    pub texture_origin: TextureOrigin,
+}
+
+impl Conventions {
+    pub const OPENGL: Self = Self {
+        handedness: Handedness::Right,
+        up_axis: UpAxis::Y,
+        depth: DepthConvention::NegativeOneToOne,
+        ndc_y: NdcY::Up,
+        texture_origin: TextureOrigin::BottomLeft,
+    };
+
+    pub const VULKAN: Self = Self {
+        handedness: Handedness::Right,
+        up_axis: UpAxis::Y,
+        depth: DepthConvention::ZeroToOne,
+        ndc_y: NdcY::Down,
+        texture_origin: TextureOrigin::TopLeft,
+    };
+
+    pub const DIRECTX: Self = Self {
+        handedness: Handedness::Left,
+        up_axis: UpAxis::Y,
+        depth: DepthConvention::ZeroToOne,
+        ndc_y: NdcY::Up,
+        texture_origin: TextureOrigin::TopLeft,
+    };
+
+    pub const METAL: Self = Self {
+        handedness: Handedness::Right,
+        up_axis: UpAxis::Y,
+        depth: DepthConvention::ZeroToOne,
+        ndc_y: NdcY::Up,
+        texture_origin: TextureOrigin::TopLeft,
+    };
+
+    pub const WGPU: Self = Self::METAL;
+
+    /// Returns the world up direction
+    pub fn up(&self) -> Vec3f32 {
+        match self.up_axis {
+            UpAxis::Y => Vec3f32::new(0.0, 1.0, 0.0),
+            UpAxis::Z => Vec3f32::new(0.0, 0.0, 1.0),
+        }
+    }
+
+    /// The sign of view space z in front of the camera
+    fn forward_sign(&self) -> f32 {
+        match self.handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        }
+    }
+
+    fn ndc_y_sign(&self) -> f32 {
+        match self.ndc_y {
+            NdcY::Up => 1.0,
+            NdcY::Down => -1.0,
+        }
+    }
+
+    /// Returns the view matrix for a camera at eye looking at target, with
+    /// the world up axis pointing up on the screen.
+    /// Make sure the view direction is not parallel to the up axis.
+    pub fn look_at(&self, eye: Vec3f32, target: Vec3f32) -> Mat4f32 {
+        self.look_at_up(eye, target, self.up())
+    }
+
+    #[rustfmt::skip]
+    fn look_at_up(&self, eye: Vec3f32, target: Vec3f32, up: Vec3f32) -> Mat4f32 {
+        match self.handedness {
+            Handedness::Right => Mat4f32::look_at(eye, target, up),
+            Handedness::Left => {
+                let mut f = target - eye;
+                f.normalize();
+                let mut r = up.cross(f);
+                r.normalize();
+                let u = f.cross(r);
+                Mat4f32 { values: [
+                    r.x, r.y, r.z, -r.dot(eye),
+                    u.x, u.y, u.z, -u.dot(eye),
+                    f.x, f.y, f.z, -f.dot(eye),
+                    0.0, 0.0, 0.0, 1.0,
+                ]}
+            }
+        }
+    }
+
+    /// Returns a perspective projection with the vertical field of view
+    /// fov_y (in radians)
+    pub fn perspective(&self, fov_y: f32, aspect_ratio: f32, near: f32, far: f32) -> Mat4f32 {
+        let top = near * float::tan(fov_y / 2.0);
+        let right = top * aspect_ratio;
+        self.frustum(-right, right, -top, top, near, far)
+    }
+
+    /// Returns a perspective projection of the (possibly asymmetric) view
+    /// frustum whose near plane spans left..right and bottom..top, for VR
+    /// eyes and off-axis projections
+    #[rustfmt::skip]
+    pub fn frustum(&self, left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4f32 {
+        let (w, h) = (right - left, top - bottom);
+        let (s, y) = (self.forward_sign(), self.ndc_y_sign());
+        // Clip space depth as a * distance + b, divided by the distance
+        let (a, b) = match self.depth {
+            DepthConvention::ZeroToOne => (far / (far - near), -near * far / (far - near)),
+            DepthConvention::NegativeOneToOne => {
+                ((far + near) / (far - near), -2.0 * far * near / (far - near))
+            }
+            DepthConvention::Reversed => (near / (near - far), near * far / (far - near)),
+        };
+        Mat4f32 { values: [
+            2.0 * near / w, 0.0, -s * (right + left) / w, 0.0,
+            0.0, y * 2.0 * near / h, -y * s * (top + bottom) / h, 0.0,
+            0.0, 0.0, s * a, b,
+            0.0, 0.0, s, 0.0,
+        ]}
+    }
+
+    /// Returns an orthographic projection of the view space box
+    /// left..right, bottom..top and near..far (distances in front of the camera)
+    #[rustfmt::skip]
+    pub fn orthographic(&self, left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4f32 {
+        let (w, h, d) = (right - left, top - bottom, far - near);
+        let (s, y) = (self.forward_sign(), self.ndc_y_sign());
+        // Clip space depth as a * distance + b
+        let (a, b) = match self.depth {
+            DepthConvention::ZeroToOne => (1.0 / d, -near / d),
+            DepthConvention::NegativeOneToOne => (2.0 / d, -(far + near) / d),
+            DepthConvention::Reversed => (-1.0 / d, far / d),
+        };
+        Mat4f32 { values: [
+            2.0 / w, 0.0, 0.0, -(right + left) / w,
+            0.0, y * 2.0 / h, 0.0, -y * (top + bottom) / h,
+            0.0, 0.0, s * a, b,
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Maps normalized device coordinates to pixel coordinates (origin in
+    /// the top left corner) and window depth
+    pub fn ndc_to_pixel(&self, viewport: &Viewport, ndc: Vec3f32) -> Vec3f32 {
+        let z = match self.depth {
+            DepthConvention::NegativeOneToOne => (ndc.z + 1.0) * 0.5,
+            _ => ndc.z,
+        };
+        viewport.ndc_to_pixel(Vec3f32::new(ndc.x, ndc.y * self.ndc_y_sign(), z))
+    }
+
+    /// The inverse of ndc_to_pixel
+    pub fn pixel_to_ndc(&self, viewport: &Viewport, pixel: Vec3f32) -> Vec3f32 {
+        let ndc = viewport.pixel_to_ndc(pixel);
+        let z = match self.depth {
+            DepthConvention::NegativeOneToOne => ndc.z * 2.0 - 1.0,
+            _ => ndc.z,
+        };
+        Vec3f32::new(ndc.x, ndc.y * self.ndc_y_sign(), z)
+    }
+
+    /// Projects a world space position to the screen, see render::project::project
+    pub fn project(&self, world_pos: Vec3f32, view_proj: &Mat4f32, viewport: &Viewport) -> Vec3f32 {
+        self.ndc_to_pixel(viewport, view_proj.transform_point_projective(world_pos))
+    }
+
+    /// Returns the world space position of a pixel at the window depth,
+    /// the inverse of project
+    pub fn unproject(
+        &self,
+        screen_pos: Vec2f32,
+        depth: f32,
+        viewport: &Viewport,
+        inv_view_proj: &Mat4f32,
+    ) -> Vec3f32 {
+        let ndc = self.pixel_to_ndc(viewport, Vec3f32::new(screen_pos.x, screen_pos.y, depth));
+        inv_view_proj.transform_point_projective(ndc)
+    }
+
+    /// Returns the ray through a pixel, starting on the near plane with a
+    /// normalized direction
+    pub fn picking_ray(
+        &self,
+        screen_pos: Vec2f32,
+        viewport: &Viewport,
+        inv_view_proj: &Mat4f32,
+    ) -> Ray3f32 {
+        let (near_depth, far_depth) = match self.depth {
+            DepthConvention::Reversed => (viewport.max_depth, viewport.min_depth),
+            _ => (viewport.min_depth, viewport.max_depth),
+        };
+        let near = self.unproject(screen_pos, near_depth, viewport, inv_view_proj);
+        let far = self.unproject(screen_pos, far_depth, viewport, inv_view_proj);
+        let mut dir = far - near;
+        dir.normalize();
+        Ray3f32::new(near, dir)
+    }
+
+    /// Returns the view matrices for rendering the six faces of a cubemap
+    /// centered at position, in the layer order +x, -x, +y, -y, +z, -z.
+    /// Use them together with cubemap_projection.
+    pub fn cubemap_face_views(&self, position: Vec3f32) -> [Mat4f32; 6] {
+        std::array::from_fn(|i| {
+            let (dir, up) = cubemap::face_vectors(i);
+            // Keeps the face from being mirrored, as view space x is
+            // flipped for left-handed views
+            let up = match self.handedness {
+                Handedness::Right => up,
+                Handedness::Left => -up,
+            };
+            self.look_at_up(position, position + dir, up)
+        })
+    }
+
+    /// Returns the 90 degree projection matching cubemap_face_views. It
+    /// mirrors y when needed, in which case the triangle winding flips and
+    /// front faces have to be swapped while rendering the faces.
+    pub fn cubemap_projection(&self, near: f32, far: f32) -> Mat4f32 {
+        let mut result = self.perspective(FRAC_PI_2, 1.0, near, far);
+        if (self.texture_origin == TextureOrigin::TopLeft) != (self.handedness == Handedness::Left)
+        {
+            result.values[5] = -result.values[5];
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::depth::linearize_depth;
+
+    const PRESETS: [Conventions; 4] = [
+        Conventions::OPENGL,
+        Conventions::VULKAN,
+        Conventions::DIRECTX,
+        Conventions::WGPU,
+    ];
+
+    fn assert_same(a: &Mat4f32, b: &Mat4f32) {
+        for (x, y) in a.values.iter().zip(b.values.iter()) {
+            assert!((-0.0001..0.0001).contains(&(x - y)));
+        }
+    }
+
+    #[test]
+    fn test_wgpu_matches_defaults() {
+        let c = Conventions::WGPU;
+        let eye = Vec3f32::new(1.0, 2.0, 3.0);
+        let target = Vec3f32::new(-2.0, 0.5, 1.0);
+        assert_same(
+            &c.look_at(eye, target),
+            &Mat4f32::look_at(eye, target, c.up()),
+        );
+        assert_same(
+            &c.perspective(1.1, 1.5, 0.1, 100.0),
+            &Mat4f32::perspective(1.1, 1.5, 0.1, 100.0),
+        );
+        assert_same(
+            &c.orthographic(-2.0, 3.0, -1.0, 4.0, 0.5, 20.0),
+            &Mat4f32::orthographic(-2.0, 3.0, -1.0, 4.0, 0.5, 20.0),
+        );
+    }
+
+    #[test]
+    fn test_frustum() {
+        let (near, far) = (0.5, 20.0);
+        for c in PRESETS {
+            let proj = c.frustum(-0.2, 0.6, -0.1, 0.3, near, far);
+            // The top right corner of the near plane
+            let forward = c.forward_sign() * near;
+            let ndc = proj.transform_point_projective(Vec3f32::new(0.6, 0.3, forward));
+            assert!((0.9999..1.0001).contains(&ndc.x));
+            assert!((0.9999..1.0001).contains(&(ndc.y * c.ndc_y_sign())));
+            let ndc = proj.transform_point_projective(Vec3f32::new(-0.2, -0.1, forward));
+            assert!((-1.0001..-0.9999).contains(&ndc.x));
+            let distance = linearize_depth(ndc.z, near, far, c.depth);
+            assert!((0.4999..0.5001).contains(&distance));
+        }
+    }
+
+    #[test]
+    fn test_presets() {
+        let viewport = Viewport::new(0.0, 0.0, 200.0, 100.0);
+        let (near, far) = (0.5, 50.0);
+        let eye = Vec3f32::new(1.0, 2.0, 3.0);
+        let forward = Vec3f32::new(0.0, 0.0, -1.0);
+
+        for c in PRESETS {
+            let view = c.look_at(eye, eye + forward);
+            let projections = [
+                c.perspective(1.0, 2.0, near, far),
+                c.orthographic(-10.0, 10.0, -5.0, 5.0, near, far),
+            ];
+            for (i, proj) in projections.iter().enumerate() {
+                let view_proj = *proj * view;
+                let inverse = view_proj.inverse().unwrap();
+
+                // Points above the camera end up in the upper half of the screen
+                let p = c.project(eye + forward * 10.0 + c.up(), &view_proj, &viewport);
+                assert!(p.y < 50.0);
+                let back = c.unproject(Vec2f32::new(p.x, p.y), p.z, &viewport, &inverse);
+                assert!((-0.001..0.001).contains(&back.distance(eye + forward * 10.0 + c.up())));
+
+                let ray = c.picking_ray(Vec2f32::new(100.0, 50.0), &viewport, &inverse);
+                assert!((-0.001..0.001).contains(&ray.origin.distance(eye + forward * near)));
+                assert!((-0.0001..0.0001).contains(&ray.dir.distance(forward)));
+
+                if i == 0 {
+                    let ndc = view_proj.transform_point_projective(eye + forward * 7.3);
+                    let distance = linearize_depth(ndc.z, near, far, c.depth);
+                    assert!((7.299..7.301).contains(&distance));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cubemap_faces() {
+        let position = Vec3f32::new(1.0, 2.0, 3.0);
+        for c in PRESETS {
+            let views = c.cubemap_face_views(position);
+            let proj = c.cubemap_projection(0.1, 10.0);
+            // Texture coordinates with v = 0 in the first row of the face
+            let row_zero_up = (c.texture_origin == TextureOrigin::TopLeft) == (c.ndc_y == NdcY::Up);
+            let uv = |face: usize, dir: Vec3f32| {
+                let ndc = (proj * views[face]).transform_point_projective(position + dir);
+                let y = if row_zero_up { ndc.y } else { -ndc.y };
+                Vec2f32::new((ndc.x + 1.0) * 0.5, (1.0 - y) * 0.5)
+            };
+
+            // On the +x face, u points to -z and v points to -y
+            let p = uv(0, Vec3f32::new(1.0, 0.5, -0.5));
+            assert!((0.7499..0.7501).contains(&p.x));
+            assert!((0.2499..0.2501).contains(&p.y));
+
+            // On the +y face, u points to +x and v points to +z
+            let p = uv(2, Vec3f32::new(0.5, 1.0, 0.5));
+            assert!((0.7499..0.7501).contains(&p.x));
+            assert!((0.7499..0.7501).contains(&p.y));
+        }
+    }
+}
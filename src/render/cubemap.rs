@@ -0,0 +1,135 @@
+use crate::{
+    mat::mat4f32::Mat4f32,
+    render::conventions::Conventions,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// View direction and up vector of each cubemap face in the usual layer
+/// order +x, -x, +y, -y, +z, -z
+const FACES: [([f32; 3], [f32; 3]); 6] = [
+    ([1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+    ([-1.0, 0.0, 0.0], [0.0, -1.0, 0.0]),
+    ([0.0, 1.0, 0.0], [0.0, 0.0, 1.0]),
+    ([0.0, -1.0, 0.0], [0.0, 0.0, -1.0]),
+    ([0.0, 0.0, 1.0], [0.0, -1.0, 0.0]),
+    ([0.0, 0.0, -1.0], [0.0, -1.0, 0.0]),
+];
+
+pub(crate) fn face_vectors(index: usize) -> (Vec3f32, Vec3f32) {
+    let ([dx, dy, dz], [ux, uy, uz]) = FACES[index];
+    (Vec3f32::new(dx, dy, dz), Vec3f32::new(ux, uy, uz))
+}
+
+/// Returns the view matrices for rendering the six faces of a cubemap
+/// centered at position, in the layer order +x, -x, +y, -y, +z, -z.
+/// Use them together with cubemap_projection.
+pub fn cubemap_face_views(position: Vec3f32) -> [Mat4f32; 6] {
+    Conventions::WGPU.cubemap_face_views(position)
+}
+
+/// Returns the 90 degree projection matching cubemap_face_views for wgpu,
+/// Direct3D and Metal. The projection mirrors y, so the triangle winding
+/// flips and front faces have to be swapped while rendering the faces.
+/// For other backends, use Conventions::cubemap_projection instead.
+pub fn cubemap_projection(near: f32, far: f32) -> Mat4f32 {
+    Conventions::WGPU.cubemap_projection(near, far)
+}
+
+/// Returns the cubemap face (in the layer order +x, -x, +y, -y, +z, -z)
+/// that the direction dir samples and the texture coordinates on it, with
+/// the texture origin in the top left corner of each face. This is the
+/// face selection all graphics APIs share, and matches cubemap_face_views.
+/// Make sure dir is not 0.
+pub fn dir_to_cubemap_face_uv(dir: Vec3f32) -> (usize, Vec2f32) {
+    let (x, y, z) = (dir.x.abs(), dir.y.abs(), dir.z.abs());
+    // The major axis and the directions of u and v on the face
+    let (face, major, u, v) = if x >= y && x >= z {
+        if dir.x > 0.0 {
+            (0, x, -dir.z, -dir.y)
+        } else {
+            (1, x, dir.z, -dir.y)
+        }
+    } else if y >= z {
+        if dir.y > 0.0 {
+            (2, y, dir.x, dir.z)
+        } else {
+            (3, y, dir.x, -dir.z)
+        }
+    } else if dir.z > 0.0 {
+        (4, z, dir.x, -dir.y)
+    } else {
+        (5, z, -dir.x, -dir.y)
+    };
+    (
+        face,
+        Vec2f32::new((u / major + 1.0) * 0.5, (v / major + 1.0) * 0.5),
+    )
+}
+
+/// The inverse of dir_to_cubemap_face_uv, returns a normalized direction
+pub fn cubemap_face_uv_to_dir(face: usize, uv: Vec2f32) -> Vec3f32 {
+    let (u, v) = (uv.x * 2.0 - 1.0, uv.y * 2.0 - 1.0);
+    let mut dir = match face {
+        0 => Vec3f32::new(1.0, -v, -u),
+        1 => Vec3f32::new(-1.0, -v, u),
+        2 => Vec3f32::new(u, 1.0, v),
+        3 => Vec3f32::new(u, -1.0, -v),
+        4 => Vec3f32::new(u, -v, 1.0),
+        _ => Vec3f32::new(-u, -v, -1.0),
+    };
+    dir.normalize();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_orientation() {
+        let position = Vec3f32::new(1.0, 2.0, 3.0);
+        let views = cubemap_face_views(position);
+        let proj = cubemap_projection(0.1, 10.0);
+
+        for (i, view) in views.iter().enumerate() {
+            let (dir, _) = face_vectors(i);
+            let center = (proj * *view).transform_point_projective(position + dir);
+            assert!((-0.0001..0.0001).contains(&center.x));
+            assert!((-0.0001..0.0001).contains(&center.y));
+        }
+
+        // On the +x face, u points to -z and v (downwards) points to -y
+        let p = position + Vec3f32::new(1.0, 0.5, -0.5);
+        let ndc = (proj * views[0]).transform_point_projective(p);
+        assert!((0.4999..0.5001).contains(&ndc.x));
+        assert!((0.4999..0.5001).contains(&ndc.y));
+
+        // On the +y face, u points to +x and v points to +z
+        let p = position + Vec3f32::new(0.5, 1.0, 0.5);
+        let ndc = (proj * views[2]).transform_point_projective(p);
+        assert!((0.4999..0.5001).contains(&ndc.x));
+        assert!((-0.5001..-0.4999).contains(&ndc.y));
+    }
+
+    #[test]
+    fn test_face_uv() {
+        let position = Vec3f32::new(0.0, 0.0, 0.0);
+        let views = cubemap_face_views(position);
+        let proj = cubemap_projection(0.1, 10.0);
+        for i in 0..50 {
+            let t = i as f32;
+            let mut dir = Vec3f32::new((t * 1.3).sin(), (t * 0.7).cos(), (t * 2.1).sin() - 0.2);
+            dir.normalize();
+            let (face, uv) = dir_to_cubemap_face_uv(dir);
+
+            // The same texel the face was rendered to (NDC y up, texture
+            // origin in the top left corner)
+            let ndc = (proj * views[face]).transform_point_projective(dir);
+            assert!((-0.0001..0.0001).contains(&(uv.x - (ndc.x + 1.0) * 0.5)));
+            assert!((-0.0001..0.0001).contains(&(uv.y - (1.0 - ndc.y) * 0.5)));
+
+            let back = cubemap_face_uv_to_dir(face, uv);
+            assert!((-0.0001..0.0001).contains(&back.distance(dir)));
+        }
+    }
+}
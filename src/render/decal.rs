@@ -0,0 +1,58 @@
+use crate::{mat::mat4f32::Mat4f32, transform::transform3df32::Transform3Df32, vec::vec3::Vec3f32};
+
+/// Returns the matrix that maps world positions into the UVW space of a
+/// decal box, for decals and projected textures. The box spans
+/// -half_extents..half_extents in the local space of transform and projects
+/// the texture along its local -z axis. u goes from 0 to 1 along local +x,
+/// v along local -y (texture origin in the top left corner) and w from the
+/// +z face to the -z face.
+/// Positions outside of 0..1 on any axis are outside of the box and should
+/// be discarded. Returns None if the box has no volume.
+#[rustfmt::skip]
+pub fn decal_projector(transform: &Transform3Df32, half_extents: Vec3f32) -> Option<Mat4f32> {
+    let world_to_local = transform.to_mat4().inverse()?;
+    let (x, y, z) = (half_extents.x, half_extents.y, half_extents.z);
+    if x == 0.0 || y == 0.0 || z == 0.0 {
+        return None;
+    }
+    // Scales the box to -1..1 and then biases it to 0..1, flipping y and z
+    let bias = Mat4f32 { values: [
+        0.5 / x, 0.0, 0.0, 0.5,
+        0.0, -0.5 / y, 0.0, 0.5,
+        0.0, 0.0, -0.5 / z, 0.5,
+        0.0, 0.0, 0.0, 1.0,
+    ]};
+    Some(bias * world_to_local)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rotor::rot3df32::Rot3Df32;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_decal_projector() {
+        // A decal on the floor projecting downwards, rotated so that local
+        // -z points along world -y and local +y along world -z
+        let transform = Transform3Df32::new(
+            Vec3f32::new(10.0, 0.0, 5.0),
+            Rot3Df32::from_axis_angle(Vec3f32::new(1.0, 0.0, 0.0), -FRAC_PI_2),
+            Vec3f32::new(2.0, 2.0, 1.0),
+        );
+        let projector = decal_projector(&transform, Vec3f32::new(1.0, 0.5, 0.25)).unwrap();
+
+        let center = projector.transform_point(Vec3f32::new(10.0, 0.0, 5.0));
+        assert!((-0.0001..0.0001).contains(&center.distance(Vec3f32::new(0.5, 0.5, 0.5))));
+
+        // The top left corner of the image on the top face of the box
+        let corner = projector.transform_point(Vec3f32::new(8.0, 0.25, 4.0));
+        assert!((-0.0001..0.0001).contains(&corner.distance(Vec3f32::new(0.0, 0.0, 0.0))));
+
+        let corner = projector.transform_point(Vec3f32::new(12.0, -0.25, 6.0));
+        assert!((-0.0001..0.0001).contains(&corner.distance(Vec3f32::new(1.0, 1.0, 1.0))));
+
+        let flat = Transform3Df32::identity();
+        assert!(decal_projector(&flat, Vec3f32::new(1.0, 1.0, 0.0)).is_none());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    mat::mat4f32::Mat4f32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// How a perspective projection maps view distance to depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthConvention {
+    /// Near maps to 0 and far to 1 (wgpu, Vulkan, Direct3D, Metal)
+    ZeroToOne,
+    /// Near maps to -1 and far to 1 (OpenGL)
+    NegativeOneToOne,
+    /// Near maps to 1 and far to 0, for better precision in the distance
+    Reversed,
+}
+
+/// Returns the positive view space distance for a depth value produced by
+/// a perspective projection with the given near and far distances
+pub fn linearize_depth(ndc_depth: f32, near: f32, far: f32, convention: DepthConvention) -> f32 {
+    match convention {
+        DepthConvention::ZeroToOne => far * near / (far - ndc_depth * (far - near)),
+        DepthConvention::NegativeOneToOne => {
+            2.0 * far * near / (far + near - ndc_depth * (far - near))
+        }
+        DepthConvention::Reversed => far * near / (near + ndc_depth * (far - near)),
+    }
+}
+
+/// Returns the view space z coordinate for a depth value. It is negative,
+/// because the camera looks down -z.
+pub fn view_z_from_depth(ndc_depth: f32, near: f32, far: f32, convention: DepthConvention) -> f32 {
+    -linearize_depth(ndc_depth, near, far, convention)
+}
+
+/// Returns the view space position of a pixel given its texture
+/// coordinates (0..1, origin in the top left corner) and its depth value.
+/// Works for every projection and convention, as long as inv_proj is the
+/// inverse of the matrix that produced the depth.
+pub fn reconstruct_view_position(uv: Vec2f32, ndc_depth: f32, inv_proj: &Mat4f32) -> Vec3f32 {
+    let ndc = Vec3f32::new(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, ndc_depth);
+    inv_proj.transform_point_projective(ndc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_linearize_depth() {
+        let (near, far) = (0.5, 50.0);
+        let proj = Mat4f32::perspective(1.0, 1.5, near, far);
+        let gl_proj = Mat4f32 { values: [
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, (far + near) / (near - far), 2.0 * far * near / (near - far),
+            0.0, 0.0, -1.0, 0.0,
+        ]};
+
+        for distance in [0.5, 1.0, 7.3, 50.0] {
+            let p = Vec3f32::new(0.0, 0.0, -distance);
+            let depth = proj.transform_point_projective(p).z;
+            let gl_depth = gl_proj.transform_point_projective(p).z;
+            let range = distance * 0.9999..distance * 1.0001;
+
+            assert!(range.contains(&linearize_depth(depth, near, far, DepthConvention::ZeroToOne)));
+            assert!(range.contains(&linearize_depth(gl_depth, near, far, DepthConvention::NegativeOneToOne)));
+            assert!(range.contains(&linearize_depth(1.0 - depth, near, far, DepthConvention::Reversed)));
+            assert!(range.contains(&-view_z_from_depth(depth, near, far, DepthConvention::ZeroToOne)));
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_view_position() {
+        let proj = Mat4f32::perspective(1.2, 16.0 / 9.0, 0.1, 100.0);
+        let inverse = proj.inverse().unwrap();
+        let p = Vec3f32::new(1.5, -0.7, -12.0);
+
+        let ndc = proj.transform_point_projective(p);
+        let uv = Vec2f32::new((ndc.x + 1.0) / 2.0, (1.0 - ndc.y) / 2.0);
+        let reconstructed = reconstruct_view_position(uv, ndc.z, &inverse);
+        assert!((-0.001..0.001).contains(&p.distance(reconstructed)));
+    }
+}
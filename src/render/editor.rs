@@ -0,0 +1,131 @@
+use crate::{
+    geometry::{line3f32::Line3f32, plane::Plane, ray3f32::Ray3f32},
+    mat::mat4f32::Mat4f32,
+    render::viewport::Viewport,
+    vec::vec3::Vec3f32,
+};
+
+/// Returns where the ray (for example a picking_ray) hits the ground plane
+/// y = height, snapped to a grid with the given cell size in x and z.
+/// Pass a cell size of 0 to disable snapping. Returns None if the ray
+/// misses the plane (looking at the horizon or away from the ground).
+pub fn pick_ground(ray: &Ray3f32, height: f32, cell_size: f32) -> Option<Vec3f32> {
+    let ground = Plane::new(Vec3f32::new(0.0, 1.0, 0.0), -height);
+    let point = ray.intersect_plane(&ground)?.point;
+    if cell_size <= 0.0 {
+        return Some(point);
+    }
+    let snapped = point.snap_to_grid(cell_size);
+    Some(Vec3f32::new(snapped.x, height, snapped.z))
+}
+
+/// Returns the world space size of one pixel at world_pos, for perspective
+/// and orthographic projections. Multiply it with a size in pixels to draw
+/// gizmos and handles with a constant size on the screen.
+/// Make sure view only rotates and translates.
+pub fn pixel_world_size(
+    world_pos: Vec3f32,
+    view: &Mat4f32,
+    proj: &Mat4f32,
+    viewport: &Viewport,
+) -> f32 {
+    let p = view.transform_point(world_pos);
+    let v = &proj.values;
+    // One pixel spans 2 / height in NDC, which is clip space divided by w
+    let w = v[12] * p.x + v[13] * p.y + v[14] * p.z + v[15];
+    2.0 * w.abs() / (v[5].abs() * viewport.height)
+}
+
+/// Hit tests the handles of a translation gizmo at origin, the segments
+/// from origin along +x, +y and +z with the given length. Returns the
+/// index (0 for x, 1 for y, 2 for z) of the handle passing closest to the
+/// ray, if it passes within pick_radius (in world units, see
+/// pixel_world_size).
+pub fn pick_gizmo_axis(
+    ray: &Ray3f32,
+    origin: Vec3f32,
+    length: f32,
+    pick_radius: f32,
+) -> Option<usize> {
+    let ray_line = Line3f32::new(ray.origin, ray.dir);
+    let axes = [
+        Vec3f32::new(1.0, 0.0, 0.0),
+        Vec3f32::new(0.0, 1.0, 0.0),
+        Vec3f32::new(0.0, 0.0, 1.0),
+    ];
+    let mut best = None;
+    let mut best_distance = pick_radius;
+    for (i, &axis) in axes.iter().enumerate() {
+        let handle = Line3f32::new(origin, axis);
+        // The point of the handle closest to the ray, clamped to the segment
+        let along = match ray_line.closest_points(&handle) {
+            Some((_, q)) => (q - origin).dot(axis).clamp(0.0, length),
+            None => 0.0,
+        };
+        let point = origin + axis * along;
+        if (point - ray.origin).dot(ray.dir) < 0.0 {
+            continue;
+        }
+        let distance = ray_line.distance_to_point(point);
+        if distance <= best_distance {
+            best = Some(i);
+            best_distance = distance;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        render::project::{picking_ray, project},
+        vec::vec2::Vec2f32,
+    };
+
+    #[test]
+    fn test_pick_ground() {
+        let mut dir = Vec3f32::new(0.3, -1.0, -0.45);
+        dir.normalize();
+        let ray = Ray3f32::new(Vec3f32::new(1.0, 10.0, 2.0), dir);
+        let point = pick_ground(&ray, 0.5, 0.0).unwrap();
+        assert!((0.4999..0.5001).contains(&point.y));
+        assert!((3.8499..3.8501).contains(&point.x));
+        let snapped = pick_ground(&ray, 0.5, 1.0).unwrap();
+        assert!((-0.0001..0.0001).contains(&snapped.distance(Vec3f32::new(4.0, 0.5, -2.0))));
+        let up = Ray3f32::new(ray.origin, Vec3f32::new(0.0, 1.0, 0.0));
+        assert!(pick_ground(&up, 0.5, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_pixel_size_and_gizmo() {
+        let viewport = Viewport::new(0.0, 0.0, 800.0, 600.0);
+        let view = Mat4f32::look_at(
+            Vec3f32::new(0.0, 0.0, 10.0),
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Vec3f32::new(0.0, 1.0, 0.0),
+        );
+        let proj = Mat4f32::perspective(std::f32::consts::FRAC_PI_2, 800.0 / 600.0, 0.1, 100.0);
+        // The visible height at distance 10 is 20 with a 90 degree fov
+        let size = pixel_world_size(Vec3f32::new(0.0, 0.0, 0.0), &view, &proj, &viewport);
+        assert!((0.0333..0.0334).contains(&size));
+        let ortho = Mat4f32::orthographic(-4.0, 4.0, -3.0, 3.0, 0.1, 100.0);
+        let size = pixel_world_size(Vec3f32::new(5.0, 1.0, -20.0), &view, &ortho, &viewport);
+        assert!((0.0099..0.0101).contains(&size));
+
+        // Pick the tip of the y handle of a gizmo at the origin
+        let inverse = (proj * view).inverse().unwrap();
+        let tip = project(Vec3f32::new(0.0, 1.8, 0.0), &(proj * view), &viewport);
+        let ray = picking_ray(Vec2f32::new(tip.x + 2.0, tip.y), &viewport, &inverse);
+        let radius = 5.0 * pixel_world_size(Vec3f32::new(0.0, 0.0, 0.0), &view, &proj, &viewport);
+        assert_eq!(
+            pick_gizmo_axis(&ray, Vec3f32::new(0.0, 0.0, 0.0), 2.0, radius),
+            Some(1)
+        );
+        let ray = picking_ray(Vec2f32::new(tip.x + 20.0, tip.y), &viewport, &inverse);
+        assert_eq!(
+            pick_gizmo_axis(&ray, Vec3f32::new(0.0, 0.0, 0.0), 2.0, radius),
+            None
+        );
+    }
+}
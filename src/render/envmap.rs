@@ -0,0 +1,48 @@
+use crate::{
+    float, math,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+use std::f32::consts::{PI, TAU};
+
+/// Returns the texture coordinates of the direction dir in an
+/// equirectangular (latitude-longitude) panorama. u wraps around the y
+/// axis, 0.5 looking down -z and 0.75 looking down +x, v goes from 0 at +y
+/// (the top row) to 1 at -y.
+/// Make sure dir is normalized.
+pub fn dir_to_equirect_uv(dir: Vec3f32) -> Vec2f32 {
+    Vec2f32::new(
+        0.5 + float::atan2(dir.x, -dir.z) / TAU,
+        math::safe_acos(dir.y) / PI,
+    )
+}
+
+/// The inverse of dir_to_equirect_uv, returns a normalized direction
+pub fn equirect_uv_to_dir(uv: Vec2f32) -> Vec3f32 {
+    let (sin_phi, cos_phi) = float::sin_cos((uv.x - 0.5) * TAU);
+    let (sin_theta, cos_theta) = float::sin_cos(uv.y * PI);
+    Vec3f32::new(sin_theta * sin_phi, cos_theta, -sin_theta * cos_phi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equirect() {
+        let forward = dir_to_equirect_uv(Vec3f32::new(0.0, 0.0, -1.0));
+        assert!((0.4999..0.5001).contains(&forward.x));
+        assert!((0.4999..0.5001).contains(&forward.y));
+        let right = dir_to_equirect_uv(Vec3f32::new(1.0, 0.0, 0.0));
+        assert!((0.7499..0.7501).contains(&right.x));
+        assert!((-0.0001..0.0001).contains(&dir_to_equirect_uv(Vec3f32::new(0.0, 1.0, 0.0)).y));
+
+        for i in 0..20 {
+            let uv = Vec2f32::new(0.05 * i as f32 + 0.01, 0.045 * i as f32 + 0.03);
+            let dir = equirect_uv_to_dir(uv);
+            assert!((0.9999..1.0001).contains(&dir.magnitude()));
+            let back = dir_to_equirect_uv(dir);
+            assert!((-0.0001..0.0001).contains(&(back.x - uv.x)));
+            assert!((-0.0001..0.0001).contains(&(back.y - uv.y)));
+        }
+    }
+}
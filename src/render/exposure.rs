@@ -0,0 +1,154 @@
+use crate::float;
+
+/// The calibration constant K of reflected light meters, in the EV100
+/// formulas below
+const METER_CALIBRATION: f32 = 12.5;
+
+/// Returns the exposure value at ISO 100 that exposes luminance (in nits)
+/// as middle gray
+pub fn ev100_from_luminance(luminance: f32) -> f32 {
+    float::log2(luminance * 100.0 / METER_CALIBRATION)
+}
+
+/// The inverse of ev100_from_luminance
+pub fn luminance_from_ev100(ev100: f32) -> f32 {
+    float::exp2(ev100) * METER_CALIBRATION / 100.0
+}
+
+/// Returns the factor to multiply scene luminance with before tone mapping
+/// for a camera set to ev100 (saturation based sensitivity, so the
+/// brightest unclipped luminance maps to 1)
+pub fn exposure_from_ev100(ev100: f32) -> f32 {
+    1.0 / (1.2 * float::exp2(ev100))
+}
+
+/// The bins of a log2 luminance histogram as built for auto exposure.
+/// Bin 0 collects pixels darker than min_log2 (including black), the
+/// remaining bins split min_log2..max_log2 evenly and the last bin also
+/// collects everything brighter.
+#[derive(Debug, Clone, Copy)]
+pub struct LuminanceBins {
+    pub min_log2: f32,
+    pub max_log2: f32,
+    /// Number of bins, at least 2
+    pub count: usize,
+}
+
+impl LuminanceBins {
+    pub fn new(min_log2: f32, max_log2: f32, count: usize) -> Self {
+        Self {
+            min_log2,
+            max_log2,
+            count,
+        }
+    }
+
+    /// Returns the width of one bin in log2 luminance (stops)
+    pub fn bin_width(&self) -> f32 {
+        (self.max_log2 - self.min_log2) / (self.count - 1) as f32
+    }
+
+    /// Returns the bin of a pixel with the given luminance
+    pub fn bin(&self, luminance: f32) -> usize {
+        if luminance <= 0.0 {
+            return 0;
+        }
+        let log2 = float::log2(luminance);
+        if log2 < self.min_log2 {
+            return 0;
+        }
+        let index = ((log2 - self.min_log2) / self.bin_width()) as usize + 1;
+        index.min(self.count - 1)
+    }
+
+    /// Returns the log2 luminance at the center of the bin. Bin 0 has no
+    /// real center, its lower edge min_log2 is returned.
+    pub fn bin_log2(&self, index: usize) -> f32 {
+        if index == 0 {
+            return self.min_log2;
+        }
+        self.min_log2 + (index as f32 - 0.5) * self.bin_width()
+    }
+
+    /// Returns the EV100 of the luminance at the center of the bin
+    pub fn bin_ev100(&self, index: usize) -> f32 {
+        ev100_from_luminance(float::exp2(self.bin_log2(index)))
+    }
+
+    /// Returns the bin containing the luminance of ev100
+    pub fn bin_from_ev100(&self, ev100: f32) -> usize {
+        self.bin(luminance_from_ev100(ev100))
+    }
+
+    /// Returns the average EV100 of the histogram (one count per bin),
+    /// averaged in log space and skipping bin 0. The darkest low_fraction
+    /// and the brightest high_fraction of the pixels are ignored, so small
+    /// dark corners or bright lights don't pull the exposure, pass 0.0 for
+    /// both to average everything. Returns None if no pixels remain.
+    pub fn average_ev100(
+        &self,
+        histogram: &[u32],
+        low_fraction: f32,
+        high_fraction: f32,
+    ) -> Option<f32> {
+        let counted = histogram.iter().skip(1).map(|&c| c as f32);
+        let total: f32 = counted.clone().sum();
+        let low = total * low_fraction;
+        let high = total * (1.0 - high_fraction);
+
+        let (mut seen, mut sum, mut weight) = (0.0, 0.0, 0.0);
+        for (i, count) in counted.enumerate() {
+            // The part of this bin between the low and the high cutoff
+            let included = (seen + count).min(high) - seen.max(low);
+            seen += count;
+            if included > 0.0 {
+                sum += self.bin_ev100(i + 1) * included;
+                weight += included;
+            }
+        }
+        (weight > 0.0).then(|| sum / weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ev100() {
+        // Sunny 16: EV100 15 is a luminance of 4096 nits
+        assert!((14.9999..15.0001).contains(&ev100_from_luminance(4096.0)));
+        assert!((4095.9..4096.1).contains(&luminance_from_ev100(15.0)));
+        let exposure = exposure_from_ev100(0.0);
+        assert!((0.8333..0.8334).contains(&exposure));
+    }
+
+    #[test]
+    fn test_luminance_bins() {
+        let bins = LuminanceBins::new(-8.0, 4.0, 13);
+        assert_eq!(bins.bin(0.0), 0);
+        assert_eq!(bins.bin(0.001), 0);
+        assert_eq!(bins.bin(1.5), 9);
+        assert_eq!(bins.bin(1e6), 12);
+        assert!((0.4999..0.5001).contains(&bins.bin_log2(9)));
+        for index in 1..13 {
+            assert_eq!(bins.bin_from_ev100(bins.bin_ev100(index)), index);
+        }
+
+        let mut histogram = [0; 13];
+        histogram[0] = 1000;
+        histogram[5] = 30;
+        histogram[9] = 60;
+        histogram[12] = 10;
+        let average = bins.average_ev100(&histogram, 0.0, 0.0).unwrap();
+        let expected =
+            (bins.bin_ev100(5) * 30.0 + bins.bin_ev100(9) * 60.0 + bins.bin_ev100(12) * 10.0)
+                / 100.0;
+        assert!((-0.0001..0.0001).contains(&(average - expected)));
+
+        // Dropping the darkest 30% and the brightest 10% leaves bin 9
+        let trimmed = bins.average_ev100(&histogram, 0.3, 0.1).unwrap();
+        assert!((-0.0001..0.0001).contains(&(trimmed - bins.bin_ev100(9))));
+        assert!(bins.average_ev100(&[5, 0, 0], 0.0, 0.0).is_none());
+    }
+}
@@ -0,0 +1,12 @@
+pub mod conventions;
+pub mod cubemap;
+pub mod decal;
+pub mod depth;
+pub mod editor;
+pub mod envmap;
+pub mod exposure;
+pub mod motion;
+pub mod project;
+pub mod shadow;
+pub mod texel;
+pub mod viewport;
@@ -0,0 +1,108 @@
+use crate::{
+    mat::mat4f32::Mat4f32,
+    transform::transform3df32::Transform3Df32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// The camera matrices of the current and the previous frame, for temporal
+/// effects like motion blur and TAA
+#[derive(Debug, Clone, Copy)]
+pub struct MotionMatrices {
+    pub view_proj: Mat4f32,
+    pub prev_view_proj: Mat4f32,
+    /// Maps normalized device coordinates (with depth) of the current frame
+    /// to those of the previous frame, for reprojecting the history of
+    /// static geometry
+    pub reprojection: Mat4f32,
+}
+
+impl MotionMatrices {
+    /// Returns None if view_proj can't be inverted
+    pub fn new(view_proj: Mat4f32, prev_view_proj: Mat4f32) -> Option<Self> {
+        Some(Self {
+            view_proj,
+            prev_view_proj,
+            reprojection: prev_view_proj * view_proj.inverse()?,
+        })
+    }
+
+    /// Returns the model view projection matrices of an object for the
+    /// current and the previous frame. Transforming a vertex with both and
+    /// subtracting the results gives its motion, including the motion of
+    /// the object itself.
+    pub fn object(
+        &self,
+        transform: &Transform3Df32,
+        prev_transform: &Transform3Df32,
+    ) -> (Mat4f32, Mat4f32) {
+        (
+            self.view_proj * transform.to_mat4(),
+            self.prev_view_proj * prev_transform.to_mat4(),
+        )
+    }
+
+    /// Returns the motion vector (current minus previous position in
+    /// normalized device coordinates) of static geometry at ndc, where ndc.z
+    /// is the depth buffer value
+    pub fn velocity(&self, ndc: Vec3f32) -> Vec2f32 {
+        let prev = self.reprojection.transform_point_projective(ndc);
+        Vec2f32::new(ndc.x - prev.x, ndc.y - prev.y)
+    }
+}
+
+/// Returns the matrix that maps positions in world space on a moving object
+/// to where the same point of the object was in the previous frame.
+/// Returns None if transform can't be inverted (a scale of 0).
+pub fn object_motion(
+    transform: &Transform3Df32,
+    prev_transform: &Transform3Df32,
+) -> Option<Mat4f32> {
+    Some(prev_transform.to_mat4() * transform.to_mat4().inverse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rotor::rot3df32::Rot3Df32;
+
+    #[test]
+    fn test_motion() {
+        let proj = Mat4f32::perspective(1.0, 1.5, 0.1, 100.0);
+        let up = Vec3f32::new(0.0, 1.0, 0.0);
+        let target = Vec3f32::new(0.0, 0.0, -10.0);
+        let view = Mat4f32::look_at(Vec3f32::new(0.0, 1.0, 0.0), target, up);
+        let prev_view = Mat4f32::look_at(Vec3f32::new(-0.5, 1.0, 0.2), target, up);
+        let motion = MotionMatrices::new(proj * view, proj * prev_view).unwrap();
+
+        let world = Vec3f32::new(1.0, -0.5, -6.0);
+        let ndc = motion.view_proj.transform_point_projective(world);
+        let prev = motion.prev_view_proj.transform_point_projective(world);
+        let reprojected = motion.reprojection.transform_point_projective(ndc);
+        assert!((-0.0001..0.0001).contains(&reprojected.distance(prev)));
+        let velocity = motion.velocity(ndc);
+        assert!((-0.0001..0.0001).contains(&(velocity.x - (ndc.x - prev.x))));
+        assert!((-0.0001..0.0001).contains(&(velocity.y - (ndc.y - prev.y))));
+
+        let transform = Transform3Df32::new(
+            Vec3f32::new(2.0, 0.0, -5.0),
+            Rot3Df32::from_axis_angle(up, 0.3),
+            Vec3f32::new(1.0, 2.0, 1.0),
+        );
+        let prev_transform = Transform3Df32::new(
+            Vec3f32::new(1.5, 0.0, -5.0),
+            Rot3Df32::from_axis_angle(up, 0.2),
+            Vec3f32::new(1.0, 2.0, 1.0),
+        );
+        let local = Vec3f32::new(0.5, 0.5, -0.5);
+        let (mvp, prev_mvp) = motion.object(&transform, &prev_transform);
+        let expected = prev_transform.transform_point(local);
+        let actual = object_motion(&transform, &prev_transform)
+            .unwrap()
+            .transform_point(transform.transform_point(local));
+        assert!((-0.0001..0.0001).contains(&actual.distance(expected)));
+        let prev_ndc = prev_mvp.transform_point_projective(local);
+        let expected = motion.prev_view_proj.transform_point_projective(expected);
+        assert!((-0.0001..0.0001).contains(&prev_ndc.distance(expected)));
+        assert!(mvp.transform_point_projective(local).distance(prev_ndc) > 0.01);
+    }
+}
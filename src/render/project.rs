@@ -0,0 +1,87 @@
+use crate::{
+    geometry::ray3f32::Ray3f32,
+    mat::mat4f32::Mat4f32,
+    render::viewport::Viewport,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// Projects a world space position to the screen. Returns the pixel
+/// position in x and y and the window depth (in the depth range of the
+/// viewport) in z.
+pub fn project(world_pos: Vec3f32, view_proj: &Mat4f32, viewport: &Viewport) -> Vec3f32 {
+    viewport.ndc_to_pixel(view_proj.transform_point_projective(world_pos))
+}
+
+/// Returns the world space position of the pixel position screen_pos at
+/// the window depth (in the depth range of the viewport), the inverse of project
+pub fn unproject(
+    screen_pos: Vec2f32,
+    depth: f32,
+    viewport: &Viewport,
+    inv_view_proj: &Mat4f32,
+) -> Vec3f32 {
+    let ndc = viewport.pixel_to_ndc(Vec3f32::new(screen_pos.x, screen_pos.y, depth));
+    inv_view_proj.transform_point_projective(ndc)
+}
+
+/// Returns the ray through the pixel position screen_pos, starting on the
+/// near plane with a normalized direction
+pub fn picking_ray(screen_pos: Vec2f32, viewport: &Viewport, inv_view_proj: &Mat4f32) -> Ray3f32 {
+    let near = unproject(screen_pos, viewport.min_depth, viewport, inv_view_proj);
+    let far = unproject(screen_pos, viewport.max_depth, viewport, inv_view_proj);
+    let mut dir = far - near;
+    dir.normalize();
+    Ray3f32::new(near, dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Perspective projection looking down -z with a 90 degree field of
+    /// view, aspect ratio 2, near 1 and far 10 (depth 0..1)
+    #[rustfmt::skip]
+    fn view_proj() -> Mat4f32 {
+        let (near, far) = (1.0, 10.0);
+        Mat4f32 { values: [
+            0.5, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, far / (near - far), near * far / (near - far),
+            0.0, 0.0, -1.0, 0.0,
+        ]}
+    }
+
+    #[test]
+    fn test_project_unproject() {
+        let view_proj = view_proj();
+        let inverse = view_proj.inverse().unwrap();
+        let viewport = Viewport::new(10.0, 20.0, 200.0, 100.0);
+
+        // The top left corner of the far plane
+        let p = project(Vec3f32::new(-20.0, 10.0, -10.0), &view_proj, &viewport);
+        assert!((9.999..10.001).contains(&p.x));
+        assert!((19.999..20.001).contains(&p.y));
+        assert!((0.9999..1.0001).contains(&p.z));
+
+        let world = Vec3f32::new(1.0, -0.5, -3.0);
+        let p = project(world, &view_proj, &viewport);
+        let back = unproject(Vec2f32::new(p.x, p.y), p.z, &viewport, &inverse);
+        assert!((-0.0001..0.0001).contains(&world.distance(back)));
+    }
+
+    #[test]
+    fn test_picking_ray() {
+        let inverse = view_proj().inverse().unwrap();
+        let viewport = Viewport::new(0.0, 0.0, 200.0, 100.0);
+
+        let ray = picking_ray(Vec2f32::new(100.0, 50.0), &viewport, &inverse);
+        assert!((-1.0001..-0.9999).contains(&ray.origin.z));
+        assert!((-1.0001..-0.9999).contains(&ray.dir.z));
+
+        // The right edge of the screen is at x = 2 * -z
+        let ray = picking_ray(Vec2f32::new(200.0, 50.0), &viewport, &inverse);
+        let p = ray.at((-5.0 - ray.origin.z) / ray.dir.z);
+        assert!((9.999..10.001).contains(&p.x));
+        assert!((-5.0001..-4.9999).contains(&p.z));
+    }
+}
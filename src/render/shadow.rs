@@ -0,0 +1,219 @@
+use crate::{
+    float,
+    geometry::{aabb3f32::Aabb3f32, frustum::Frustum, sphere::Sphere},
+    mat::mat4f32::Mat4f32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// Returns the 8 world space corners of the frustum of a view projection
+/// matrix, given its inverse. Expects clip space depth to range from 0 to 1.
+/// Bit 0 of the index selects the right side, bit 1 the top side and
+/// bit 2 the far side (same order as Frustum::corners).
+pub fn frustum_corners(inv_view_proj: &Mat4f32) -> [Vec3f32; 8] {
+    Frustum::corners_from_inverse_view_proj(inv_view_proj)
+}
+
+/// Returns the corners of the part of a frustum between the view distances
+/// split_near and split_far. near and far are the distances of the planes
+/// the corners were computed for. Works for perspective and orthographic
+/// frustums, because view depth changes linearly along the side edges.
+pub fn sub_frustum_corners(
+    corners: &[Vec3f32; 8],
+    near: f32,
+    far: f32,
+    split_near: f32,
+    split_far: f32,
+) -> [Vec3f32; 8] {
+    let t_near = (split_near - near) / (far - near);
+    let t_far = (split_far - near) / (far - near);
+    std::array::from_fn(|i| {
+        let edge_near = corners[i & 3];
+        let edge_far = corners[(i & 3) | 4];
+        let t = if i & 4 == 0 { t_near } else { t_far };
+        edge_near + (edge_far - edge_near) * t
+    })
+}
+
+/// Returns the count + 1 view distances that split near..far into count
+/// cascades. lambda blends between uniform (0) and logarithmic (1) splits,
+/// values around 0.5 to 0.9 work well in practice.
+pub fn cascade_splits(near: f32, far: f32, count: usize, lambda: f32) -> Vec<f32> {
+    (0..=count)
+        .map(|i| {
+            let fraction = i as f32 / count as f32;
+            let uniform = near + (far - near) * fraction;
+            let logarithmic = near * float::powf(far / near, fraction);
+            uniform + (logarithmic - uniform) * lambda
+        })
+        .collect()
+}
+
+/// Splits the frustum of a camera into count cascades (see cascade_splits)
+/// and returns the world space corners of each
+pub fn cascade_corners(
+    inv_view_proj: &Mat4f32,
+    near: f32,
+    far: f32,
+    count: usize,
+    lambda: f32,
+) -> Vec<[Vec3f32; 8]> {
+    let corners = frustum_corners(inv_view_proj);
+    cascade_splits(near, far, count, lambda)
+        .windows(2)
+        .map(|split| sub_frustum_corners(&corners, near, far, split[0], split[1]))
+        .collect()
+}
+
+/// Returns an orthographic view projection for a directional light shining
+/// along light_dir that tightly contains all points, for example the corners
+/// of a (cascade) frustum. Add the corners of the caster bounds to points,
+/// so objects outside of the view still cast shadows into it.
+/// With texel_snap set to the shadow map resolution, the projection is fit
+/// to the bounding sphere of the points and moved in whole texels only, so
+/// shadows don't shimmer when the camera moves or rotates. This wastes some
+/// resolution compared to the tight fit.
+/// Make sure light_dir is normalized and points is not empty.
+pub fn fit_light_view_proj(
+    points: &[Vec3f32],
+    light_dir: Vec3f32,
+    texel_snap: Option<u32>,
+) -> Mat4f32 {
+    let up = if light_dir.y.abs() > 0.99 {
+        Vec3f32::new(0.0, 0.0, 1.0)
+    } else {
+        Vec3f32::new(0.0, 1.0, 0.0)
+    };
+    // The view is placed at the origin so that the texel grid stays fixed
+    // in world space as long as the light direction doesn't change
+    let origin = Vec3f32::new(0.0, 0.0, 0.0);
+    let view = Mat4f32::look_at(origin, light_dir, up);
+
+    let light_points: Vec<Vec3f32> = points.iter().map(|&p| view.transform_point(p)).collect();
+    let bounds = Aabb3f32::from_points(&light_points);
+    let (mut min, mut max) = (bounds.min, bounds.max);
+
+    if let Some(resolution) = texel_snap {
+        let sphere = Sphere::from_points(&light_points);
+        // One texel of padding on each side, snapping moves the center by
+        // less than a texel
+        let resolution = resolution.max(3) as f32;
+        let half_size = sphere.radius * resolution / (resolution - 2.0);
+        let center = sphere.center.quantize(2.0 * half_size / resolution);
+        min.x = center.x - half_size;
+        max.x = center.x + half_size;
+        min.y = center.y - half_size;
+        max.y = center.y + half_size;
+    }
+
+    // The light looks down -z, so the largest z is the closest
+    let proj = Mat4f32::orthographic(min.x, max.x, min.y, max.y, -max.z, -min.z);
+    proj * view
+}
+
+/// Moves an orthographic light view projection by less than a texel, so
+/// that texels stay at fixed world positions. Use it for light projections
+/// that were not created by fit_light_view_proj with texel_snap, to stop
+/// shadow edges from shimmering while the projection moves. The size of
+/// the projection has to stay constant for this to work.
+pub fn snap_to_texels(view_proj: &Mat4f32, resolution: u32) -> Mat4f32 {
+    let half_resolution = resolution as f32 / 2.0;
+    // The world origin is a fixed point, so snapping it to a texel corner
+    // snaps every other texel as well
+    let origin = view_proj.transform_point(Vec3f32::new(0.0, 0.0, 0.0));
+    let texel = Vec2f32::new(origin.x, origin.y) * half_resolution;
+    let offset = (texel.snap_to_grid(1.0) - texel) / half_resolution;
+    let mut result = *view_proj;
+    result.values[3] += offset.x;
+    result.values[7] += offset.y;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_cascades() {
+        // Perspective projection looking down -z with a 90 degree field of
+        // view, near 1 and far 100 (depth 0..1)
+        let (near, far) = (1.0, 100.0);
+        let proj = Mat4f32 { values: [
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, far / (near - far), near * far / (near - far),
+            0.0, 0.0, -1.0, 0.0,
+        ]};
+        let inverse = proj.inverse().unwrap();
+
+        let corners = frustum_corners(&inverse);
+        assert!((99.99..100.01).contains(&corners[7].x));
+        assert!((-100.01..-99.99).contains(&corners[7].z));
+        assert!((-1.0001..-0.9999).contains(&corners[0].x));
+
+        let splits = cascade_splits(near, far, 2, 1.0);
+        assert!((9.999..10.001).contains(&splits[1]));
+        let splits = cascade_splits(near, far, 2, 0.0);
+        assert!((50.49..50.51).contains(&splits[1]));
+
+        let cascades = cascade_corners(&inverse, near, far, 4, 0.5);
+        assert_eq!(cascades.len(), 4);
+        for pair in cascades.windows(2) {
+            // Neighbouring cascades share their boundary
+            assert!((-0.001..0.001).contains(&pair[0][7].distance(pair[1][3])));
+        }
+        assert!((-100.01..-99.99).contains(&cascades[3][4].z));
+        assert!((-100.01..-99.99).contains(&cascades[3][4].x));
+    }
+
+    #[test]
+    fn test_fit_light_view_proj() {
+        let bounds = Aabb3f32::new(Vec3f32::new(-1.0, 0.0, -3.0), Vec3f32::new(2.0, 1.0, 4.0));
+        let corners = bounds.corners();
+        let mut light_dir = Vec3f32::new(0.3, -1.0, 0.2);
+        light_dir.normalize();
+
+        for snap in [None, Some(16), Some(1024)] {
+            let view_proj = fit_light_view_proj(&corners, light_dir, snap);
+            for &corner in &corners {
+                let ndc = view_proj.transform_point(corner);
+                assert!((-1.0001..1.0001).contains(&ndc.x));
+                assert!((-1.0001..1.0001).contains(&ndc.y));
+                assert!((-0.0001..1.0001).contains(&ndc.z));
+            }
+        }
+
+        // Moving the points by less than a texel doesn't move the projection
+        let view_proj = fit_light_view_proj(&corners, light_dir, Some(64));
+        let moved: Vec<Vec3f32> = corners
+            .iter()
+            .map(|&p| p + Vec3f32::new(1e-5, 0.0, 0.0))
+            .collect();
+        let moved_view_proj = fit_light_view_proj(&moved, light_dir, Some(64));
+        let p = view_proj.transform_point(Vec3f32::new(0.0, 0.0, 0.0));
+        let q = moved_view_proj.transform_point(Vec3f32::new(0.0, 0.0, 0.0));
+        assert!((-0.0001..0.0001).contains(&(p.x - q.x)));
+        assert!((-0.0001..0.0001).contains(&(p.y - q.y)));
+    }
+
+    #[test]
+    fn test_snap_to_texels() {
+        let resolution = 256;
+        let proj = Mat4f32::orthographic(-10.0, 10.0, -10.0, 10.0, 0.1, 50.0);
+        for x in [0.0, 0.013, 3.71] {
+            let eye = Vec3f32::new(x, 20.0, 5.0);
+            let view = Mat4f32::look_at(
+                eye,
+                eye - Vec3f32::new(0.0, 1.0, 0.1),
+                Vec3f32::new(0.0, 0.0, -1.0),
+            );
+            let snapped = snap_to_texels(&(proj * view), resolution);
+            // The origin lands on a texel corner
+            let p = snapped.transform_point(Vec3f32::new(0.0, 0.0, 0.0));
+            for ndc in [p.x, p.y] {
+                let texel = ndc * resolution as f32 / 2.0;
+                assert!((-0.001..0.001).contains(&(texel - texel.round())));
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::vec::vec2::Vec2f32;
+
+/// Returns the texture coordinates of the center of the texel that uv
+/// falls into, for sampling exactly one texel with linear filtering
+pub fn texel_center(uv: Vec2f32, texture_size: Vec2f32) -> Vec2f32 {
+    Vec2f32::new(
+        ((uv.x * texture_size.x).floor() + 0.5) / texture_size.x,
+        ((uv.y * texture_size.y).floor() + 0.5) / texture_size.y,
+    )
+}
+
+/// Rounds uv to the closest texel corner, so that a texture drawn there
+/// lines up with the texel grid (pixel perfect sprites and text)
+pub fn round_to_texel(uv: Vec2f32, texture_size: Vec2f32) -> Vec2f32 {
+    Vec2f32::new(
+        (uv.x * texture_size.x).round() / texture_size.x,
+        (uv.y * texture_size.y).round() / texture_size.y,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_texel_rounding() {
+        let size = Vec2f32::new(4.0, 8.0);
+        let uv = Vec2f32::new(0.3, 0.99);
+        let center = texel_center(uv, size);
+        assert!((0.3749..0.3751).contains(&center.x));
+        assert!((0.9374..0.9376).contains(&center.y));
+
+        let rounded = round_to_texel(uv, size);
+        assert!((0.2499..0.2501).contains(&rounded.x));
+        assert!((0.9999..1.0001).contains(&rounded.y));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    geometry::rect2f32::Rect2f32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// The pixel rectangle and depth range a projection is mapped to. Screen
+/// coordinates have their origin in the top left corner with y pointing down.
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Window depth that clip space depth 0 maps to
+    pub min_depth: f32,
+    /// Window depth that clip space depth 1 maps to
+    pub max_depth: f32,
+}
+
+impl Viewport {
+    /// Creates a viewport with the depth range 0..1
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        }
+    }
+
+    pub fn from_rect(rect: &Rect2f32) -> Self {
+        Self::new(rect.min.x, rect.min.y, rect.width(), rect.height())
+    }
+
+    pub fn rect(&self) -> Rect2f32 {
+        Rect2f32::from_origin_size(
+            Vec2f32::new(self.x, self.y),
+            Vec2f32::new(self.width, self.height),
+        )
+    }
+
+    /// Returns the aspect ratio (width / height)
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width / self.height
+    }
+
+    /// Maps normalized device coordinates (x and y in -1..1 with y up,
+    /// depth 0..1) to pixel coordinates and window depth
+    pub fn ndc_to_pixel(&self, ndc: Vec3f32) -> Vec3f32 {
+        Vec3f32::new(
+            self.x + (ndc.x + 1.0) * 0.5 * self.width,
+            self.y + (1.0 - ndc.y) * 0.5 * self.height,
+            self.min_depth + ndc.z * (self.max_depth - self.min_depth),
+        )
+    }
+
+    /// The inverse of ndc_to_pixel
+    pub fn pixel_to_ndc(&self, pixel: Vec3f32) -> Vec3f32 {
+        Vec3f32::new(
+            (pixel.x - self.x) / self.width * 2.0 - 1.0,
+            1.0 - (pixel.y - self.y) / self.height * 2.0,
+            (pixel.z - self.min_depth) / (self.max_depth - self.min_depth),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ndc_pixel_conversion() {
+        let mut viewport = Viewport::new(100.0, 50.0, 800.0, 600.0);
+        viewport.min_depth = 0.5;
+
+        let top_left = viewport.ndc_to_pixel(Vec3f32::new(-1.0, 1.0, 0.0));
+        assert!((99.999..100.001).contains(&top_left.x));
+        assert!((49.999..50.001).contains(&top_left.y));
+        assert!((0.4999..0.5001).contains(&top_left.z));
+
+        let ndc = Vec3f32::new(0.25, -0.5, 0.75);
+        let back = viewport.pixel_to_ndc(viewport.ndc_to_pixel(ndc));
+        assert!((-0.0001..0.0001).contains(&ndc.distance(back)));
+
+        let rect = viewport.rect();
+        assert!((899.999..900.001).contains(&rect.max.x));
+    }
+}
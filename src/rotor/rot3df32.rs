@@ -1,4 +1,7 @@
-use crate::{mat::mat4f32::Mat4f32, vec::vec3::Vec3f32};
+use crate::{
+    float, mat::mat4f32::Mat4f32, math, transform::transform_error::TransformError,
+    vec::vec3::Vec3f32,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -65,6 +68,81 @@ impl Rot3Df32 {
         Self::new(a, b)
     }
 
+    /// Construct a new Rotor that rotates vectors counter-clockwise around
+    /// axis by angle (in radians), seen from the tip of the axis.
+    /// Make sure axis is normalized.
+    pub fn from_axis_angle(axis: Vec3f32, angle: f32) -> Self {
+        let (sin, cos) = float::sin_cos(angle / 2.0);
+        Rot3Df32 {
+            s: cos,
+            xy: -sin * axis.z,
+            yz: -sin * axis.x,
+            zx: -sin * axis.y,
+        }
+    }
+
+    /// Construct a new Rotor from a rotation vector, the axis scaled by the
+    /// angle in radians (the exponential map, inverse of to_scaled_axis)
+    pub fn from_scaled_axis(v: Vec3f32) -> Self {
+        let angle = v.magnitude();
+        if angle <= f32::EPSILON {
+            return Self::identity();
+        }
+        Self::from_axis_angle(v / angle, angle)
+    }
+
+    /// Construct a new Rotor that rotates the x, y and z unit vectors onto
+    /// x_axis, y_axis and z_axis.
+    /// Make sure the axes form an orthonormal right-handed basis.
+    pub fn from_basis(x_axis: Vec3f32, y_axis: Vec3f32, z_axis: Vec3f32) -> Self {
+        // Rows and columns of the rotation matrix with the axes as columns
+        let (m00, m01, m02) = (x_axis.x, y_axis.x, z_axis.x);
+        let (m10, m11, m12) = (x_axis.y, y_axis.y, z_axis.y);
+        let (m20, m21, m22) = (x_axis.z, y_axis.z, z_axis.z);
+
+        // Quaternion (w, x, y, z) from the matrix, choosing the largest
+        // component as the divisor for numerical stability
+        let trace = m00 + m11 + m22;
+        let (w, x, y, z) = if trace > 0.0 {
+            let s = float::sqrt(trace + 1.0) * 2.0;
+            (0.25 * s, (m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = float::sqrt(1.0 + m00 - m11 - m22) * 2.0;
+            ((m21 - m12) / s, 0.25 * s, (m01 + m10) / s, (m02 + m20) / s)
+        } else if m11 > m22 {
+            let s = float::sqrt(1.0 + m11 - m00 - m22) * 2.0;
+            ((m02 - m20) / s, (m01 + m10) / s, 0.25 * s, (m12 + m21) / s)
+        } else {
+            let s = float::sqrt(1.0 + m22 - m00 - m11) * 2.0;
+            ((m10 - m01) / s, (m02 + m20) / s, (m12 + m21) / s, 0.25 * s)
+        };
+
+        // The bivector parts are the negated quaternion vector parts
+        let mut result = Rot3Df32 {
+            s: w,
+            xy: -z,
+            yz: -x,
+            zx: -y,
+        };
+        result.normalize();
+        result
+    }
+
+    /// Returns the rotation vector, the axis scaled by the angle in radians
+    /// (the logarithmic map). The angle is in 0..=PI, so this always
+    /// describes the shortest way to the rotation.
+    pub fn to_scaled_axis(&self) -> Vec3f32 {
+        // Rotors r and -r describe the same rotation, pick the one with s >= 0
+        let sign = if self.s < 0.0 { -1.0 } else { 1.0 };
+        let v = Vec3f32::new(-self.yz, -self.zx, -self.xy) * sign;
+        let sin_half = v.magnitude();
+        if sin_half <= f32::EPSILON {
+            return v * 2.0;
+        }
+        let angle = 2.0 * float::atan2(sin_half, self.s * sign);
+        v * (angle / sin_half)
+    }
+
     /// Returns self but inverted (reverse rotation)
     pub fn inverted(&self) -> Self {
         let mut result = *self;
@@ -118,16 +196,165 @@ impl Rot3Df32 {
         self.zx = zx;
     }
 
+    /// Returns self advanced by the world space angular velocity (axis
+    /// scaled by radians per second) over dt seconds, using the exponential
+    /// map so large steps stay exact. The result is renormalized to keep
+    /// the error from building up over many frames.
+    /// For an angular velocity in local space, append the step instead.
+    pub fn integrated(&self, angular_velocity: Vec3f32, dt: f32) -> Self {
+        let mut result = Self::from_scaled_axis(angular_velocity * dt).appended(*self);
+        result.normalize();
+        result
+    }
+
+    /// Advances self by the world space angular velocity over dt seconds,
+    /// see integrated
+    pub fn integrate(&mut self, angular_velocity: Vec3f32, dt: f32) {
+        *self = self.integrated(angular_velocity, dt);
+    }
+
+    /// Returns the world space rotation that turns self into target, so
+    /// that delta.appended(self) equals target. Takes the shortest way.
+    pub fn delta_to(&self, target: Rot3Df32) -> Self {
+        let mut delta = target.appended(self.inverted());
+        if delta.s < 0.0 {
+            delta = Self {
+                s: -delta.s,
+                xy: -delta.xy,
+                yz: -delta.yz,
+                zx: -delta.zx,
+            };
+        }
+        delta
+    }
+
+    /// Returns the world space angular velocity that turns self into target
+    /// in dt seconds, the inverse of integrated
+    pub fn angular_velocity_to(&self, target: Rot3Df32, dt: f32) -> Vec3f32 {
+        self.delta_to(target).to_scaled_axis() / dt
+    }
+
+    /// Interpolates between self (t = 0) and other (t = 1) along the
+    /// shortest arc with constant angular velocity
+    pub fn slerp(&self, other: Rot3Df32, t: f32) -> Self {
+        let delta = self.delta_to(other).to_scaled_axis();
+        let mut result = Self::from_scaled_axis(delta * t).appended(*self);
+        result.normalize();
+        result
+    }
+
+    /// Interpolates linearly between self and other along the shortest arc
+    /// and normalizes the result. Cheaper than slerp, but the angular
+    /// velocity is not constant.
+    pub fn nlerp(&self, other: Rot3Df32, t: f32) -> Self {
+        let dot = self.s * other.s + self.xy * other.xy + self.yz * other.yz + self.zx * other.zx;
+        let t_other = if dot < 0.0 { -t } else { t };
+        let mut result = Self {
+            s: self.s * (1.0 - t) + other.s * t_other,
+            xy: self.xy * (1.0 - t) + other.xy * t_other,
+            yz: self.yz * (1.0 - t) + other.yz * t_other,
+            zx: self.zx * (1.0 - t) + other.zx * t_other,
+        };
+        result.normalize();
+        result
+    }
+
+    /// Returns the weighted average of the rotations. Starts with the
+    /// normalized sum of the rotors (flipped into the same hemisphere) and
+    /// refines it in log space, so the result doesn't depend on the order of
+    /// the rotors. Returns None if rotors is empty or the weights sum to 0.
+    pub fn weighted_average(rotors: &[(Rot3Df32, f32)]) -> Option<Self> {
+        let total: f32 = rotors.iter().map(|(_, w)| w).sum();
+        let &(reference, _) = rotors.first()?;
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut mean = Self {
+            s: 0.0,
+            xy: 0.0,
+            yz: 0.0,
+            zx: 0.0,
+        };
+        for &(r, w) in rotors {
+            let dot =
+                r.s * reference.s + r.xy * reference.xy + r.yz * reference.yz + r.zx * reference.zx;
+            let w = if dot < 0.0 { -w } else { w };
+            mean.s += r.s * w;
+            mean.xy += r.xy * w;
+            mean.yz += r.yz * w;
+            mean.zx += r.zx * w;
+        }
+        mean.normalize();
+
+        for _ in 0..8 {
+            let step: Vec3f32 = rotors
+                .iter()
+                .map(|&(r, w)| mean.delta_to(r).to_scaled_axis() * (w / total))
+                .sum();
+            mean = Self::from_scaled_axis(step).appended(mean);
+            mean.normalize();
+            if step.magnitude_squared() < 1e-12 {
+                break;
+            }
+        }
+        Some(mean)
+    }
+
+    /// Returns the rotation mirrored across a plane through the origin with
+    /// the given normal, so that reflecting a vector and rotating it with
+    /// the result gives the mirror image of rotating it with self. Used to
+    /// mirror orientations and animation poses from one side to the other.
+    /// Make sure normal is normalized.
+    pub fn reflected(&self, normal: Vec3f32) -> Self {
+        // The bivector part transforms like the rotation axis, which is
+        // reflected and negated (axial vector)
+        let b = Vec3f32::new(self.yz, self.zx, self.xy);
+        let b = normal * (2.0 * b.dot(normal)) - b;
+        Self {
+            s: self.s,
+            xy: b.z,
+            yz: b.x,
+            zx: b.y,
+        }
+    }
+
     /// Normalizes the rotor, doing this is pretty important
     pub fn normalize(&mut self) {
-        let mag_sqrd = self.s * self.s + self.xy * self.xy + self.yz * self.yz + self.zx * self.zx;
-        let mag = mag_sqrd.sqrt();
+        let mag = self.magnitude();
         self.s /= mag;
         self.xy /= mag;
         self.yz /= mag;
         self.zx /= mag;
     }
 
+    /// Returns true if the magnitude is 1 within eps, so the rotor
+    /// rotates without scaling
+    pub fn is_normalized(&self, eps: f32) -> bool {
+        math::approx_eq(self.magnitude(), 1.0, eps)
+    }
+
+    /// Returns the magnitude, 1 for rotors that only rotate
+    pub fn magnitude(&self) -> f32 {
+        float::sqrt(self.s * self.s + self.xy * self.xy + self.yz * self.yz + self.zx * self.zx)
+    }
+
+    /// Checks that the rotor is finite and normalized within eps
+    pub fn validate(&self, eps: f32) -> Result<(), TransformError> {
+        if ![self.s, self.xy, self.yz, self.zx]
+            .iter()
+            .all(|v| v.is_finite())
+        {
+            return Err(TransformError::NonFinite);
+        }
+        if !self.is_normalized(eps) {
+            return Err(TransformError::NotNormalized {
+                magnitude: self.magnitude(),
+            });
+        }
+        Ok(())
+    }
+
     /// Creates a 4x4 rotation matrix (3x3 and padded to make it homogenous)
     // TODO: Optimize (zero calculations)
     #[rustfmt::skip]
@@ -203,4 +430,164 @@ mod tests {
         assert!((-0.00001..0.00001).contains(&v.y));
         assert!((-0.00001..0.00001).contains(&v.z));
     }
+
+    #[test]
+    fn test_scaled_axis() {
+        let v = Vec3f32::new(0.3, -1.2, 0.8);
+        let rotor = Rot3Df32::from_scaled_axis(v);
+        let back = rotor.to_scaled_axis();
+        assert!((-0.0001..0.0001).contains(&(back - v).magnitude()));
+
+        // The negated rotor is the same rotation
+        let negated = Rot3Df32 {
+            s: -rotor.s,
+            xy: -rotor.xy,
+            yz: -rotor.yz,
+            zx: -rotor.zx,
+        };
+        assert!((-0.0001..0.0001).contains(&(negated.to_scaled_axis() - v).magnitude()));
+        assert!((-0.0001..0.0001).contains(&Rot3Df32::identity().to_scaled_axis().magnitude()));
+    }
+
+    #[test]
+    fn test_integrate() {
+        let x = Vec3f32::new(1.0, 0.0, 0.0);
+        let z = Vec3f32::new(0.0, 0.0, 1.0);
+        // Tilts x up to z first, then spins around the world z axis, which
+        // doesn't move it anymore
+        let mut r =
+            Rot3Df32::from_axis_angle(Vec3f32::new(0.0, -1.0, 0.0), std::f32::consts::FRAC_PI_2);
+        for _ in 0..100 {
+            r.integrate(z * 3.0, 0.01);
+        }
+        assert!((-0.0001..0.0001).contains(&r.rotated_vec(x).distance(z)));
+
+        let r = Rot3Df32::identity().integrated(z * std::f32::consts::FRAC_PI_2, 1.0);
+        let y = r.rotated_vec(x);
+        assert!((0.9999..1.0001).contains(&y.y));
+        let mag = r.s * r.s + r.xy * r.xy + r.yz * r.yz + r.zx * r.zx;
+        assert!((0.9999..1.0001).contains(&mag));
+    }
+
+    #[test]
+    fn test_delta_to() {
+        let mut axis = Vec3f32::new(1.0, 2.0, -0.5);
+        axis.normalize();
+        let a = Rot3Df32::from_axis_angle(axis, 0.7);
+        let b = Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 1.0, 0.0), -2.5);
+        let v = Vec3f32::new(0.3, -1.0, 2.0);
+
+        let delta = a.delta_to(b);
+        assert!(delta.s >= 0.0);
+        assert!((-0.0001..0.0001)
+            .contains(&delta.appended(a).rotated_vec(v).distance(b.rotated_vec(v))));
+
+        let velocity = a.angular_velocity_to(b, 0.5);
+        let integrated = a.integrated(velocity, 0.5);
+        assert!((-0.0001..0.0001).contains(&integrated.rotated_vec(v).distance(b.rotated_vec(v))));
+
+        let velocity = a.angular_velocity_to(a.integrated(axis * 2.0, 0.1), 0.1);
+        assert!((-0.0001..0.0001).contains(&velocity.distance(axis * 2.0)));
+    }
+
+    #[test]
+    fn test_slerp_nlerp() {
+        let z = Vec3f32::new(0.0, 0.0, 1.0);
+        let x = Vec3f32::new(1.0, 0.0, 0.0);
+        let a = Rot3Df32::from_axis_angle(z, 0.5);
+        let b = Rot3Df32::from_axis_angle(z, 2.5);
+        let expected = Rot3Df32::from_axis_angle(z, 1.0).rotated_vec(x);
+        assert!((-0.0001..0.0001).contains(&a.slerp(b, 0.25).rotated_vec(x).distance(expected)));
+
+        let halfway = Rot3Df32::from_axis_angle(z, 1.5).rotated_vec(x);
+        assert!((-0.0001..0.0001).contains(&a.nlerp(b, 0.5).rotated_vec(x).distance(halfway)));
+        let end = a.nlerp(b, 1.0).rotated_vec(x);
+        assert!((-0.0001..0.0001).contains(&end.distance(b.rotated_vec(x))));
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        let z = Vec3f32::new(0.0, 0.0, 1.0);
+        let x = Vec3f32::new(1.0, 0.0, 0.0);
+        let a = Rot3Df32::from_axis_angle(z, 0.2);
+        let b = Rot3Df32::from_axis_angle(z, 1.0);
+        let c = Rot3Df32::from_axis_angle(z, 2.0);
+        let mut negated_c = c;
+        negated_c.s = -c.s;
+        negated_c.xy = -c.xy;
+        negated_c.yz = -c.yz;
+        negated_c.zx = -c.zx;
+
+        // Rotations around one axis average like angles
+        let average = Rot3Df32::weighted_average(&[(a, 1.0), (b, 2.0), (negated_c, 1.0)]).unwrap();
+        let expected = Rot3Df32::from_axis_angle(z, (0.2 + 2.0 + 2.0) / 4.0);
+        assert!(
+            (-0.0001..0.0001).contains(&average.rotated_vec(x).distance(expected.rotated_vec(x)))
+        );
+
+        let single = Rot3Df32::weighted_average(&[(c, 0.5)]).unwrap();
+        assert!((-0.0001..0.0001).contains(&single.rotated_vec(x).distance(c.rotated_vec(x))));
+        assert!(Rot3Df32::weighted_average(&[]).is_none());
+        assert!(Rot3Df32::weighted_average(&[(a, 0.0)]).is_none());
+    }
+
+    #[test]
+    fn test_reflected() {
+        let mut normal = Vec3f32::new(1.0, -0.5, 2.0);
+        normal.normalize();
+        let reflect = |v: Vec3f32| v - normal * (2.0 * v.dot(normal));
+        let mut axis = Vec3f32::new(0.2, 1.0, 0.3);
+        axis.normalize();
+        let r = Rot3Df32::from_axis_angle(axis, 1.1);
+        let reflected = r.reflected(normal);
+
+        let v = Vec3f32::new(0.5, -2.0, 1.0);
+        let expected = reflect(r.rotated_vec(reflect(v)));
+        assert!((-0.0001..0.0001).contains(&reflected.rotated_vec(v).distance(expected)));
+    }
+
+    #[test]
+    fn test_from_axis_angle() {
+        let rotor =
+            Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2);
+        let v = rotor.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0));
+        assert!((-0.0001..0.0001).contains(&v.x));
+        assert!((0.9999..1.0001).contains(&v.y));
+        assert!((-0.0001..0.0001).contains(&v.z));
+    }
+
+    #[test]
+    fn test_from_basis() {
+        let mut a = Vec3f32::new(1.0, 2.0, -0.5);
+        a.normalize();
+        let mut b = Vec3f32::new(-0.3, 0.1, 1.0);
+        b.normalize();
+        let rotor = Rot3Df32::new_exact(a, b);
+
+        let x_axis = rotor.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0));
+        let y_axis = rotor.rotated_vec(Vec3f32::new(0.0, 1.0, 0.0));
+        let z_axis = rotor.rotated_vec(Vec3f32::new(0.0, 0.0, 1.0));
+        let result = Rot3Df32::from_basis(x_axis, y_axis, z_axis);
+
+        let v = result.rotated_vec(a);
+        assert!((-0.0001..0.0001).contains(&(v.x - b.x)));
+        assert!((-0.0001..0.0001).contains(&(v.y - b.y)));
+        assert!((-0.0001..0.0001).contains(&(v.z - b.z)));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut rotor = Rot3Df32::from_axis_angle(Vec3f32::new(1.0, 0.0, 0.0), 0.3);
+        assert!(rotor.is_normalized(0.0001));
+        assert_eq!(rotor.validate(0.0001), Ok(()));
+        rotor.s *= 2.0;
+        assert!(matches!(
+            rotor.validate(0.0001),
+            Err(TransformError::NotNormalized { .. })
+        ));
+        rotor.normalize();
+        assert_eq!(rotor.validate(0.0001), Ok(()));
+        rotor.xy = f32::INFINITY;
+        assert_eq!(rotor.validate(0.0001), Err(TransformError::NonFinite));
+    }
 }
@@ -0,0 +1,385 @@
+use crate::{
+    geometry::{aabb3f32::Aabb3f32, frustum::Frustum, intersect::intersects, ray3f32::Ray3f32},
+    vec::vec3::Vec3f32,
+};
+
+/// Maximum number of objects in a leaf
+const LEAF_SIZE: usize = 4;
+/// Number of bins per axis used to evaluate SAH split candidates
+const SAH_BINS: usize = 12;
+
+/// How nodes are split during construction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BvhSplit {
+    /// Split at the median object along the longest axis, fast to build
+    Median,
+    /// Binned surface area heuristic, slower to build but faster queries
+    Sah,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BvhNode {
+    bounds: Aabb3f32,
+    /// Index of the left child (the right child is first + 1) for interior
+    /// nodes, start of the object range in indices for leaves
+    first: usize,
+    /// Number of objects, 0 for interior nodes
+    count: usize,
+}
+
+/// Binary bounding volume hierarchy over a list of boxes. Queries return
+/// the indices of the boxes in the slice the BVH was built from.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    /// Object indices, every leaf references a contiguous range
+    indices: Vec<usize>,
+    aabbs: Vec<Aabb3f32>,
+}
+
+impl Bvh {
+    pub fn build(aabbs: &[Aabb3f32], split: BvhSplit) -> Self {
+        let origin = Vec3f32::new(0.0, 0.0, 0.0);
+        let mut bvh = Self {
+            nodes: Vec::with_capacity(2 * aabbs.len().div_ceil(LEAF_SIZE).max(1)),
+            indices: (0..aabbs.len()).collect(),
+            aabbs: aabbs.to_vec(),
+        };
+        bvh.nodes.push(BvhNode {
+            bounds: Aabb3f32::new(origin, origin),
+            first: 0,
+            count: aabbs.len(),
+        });
+        if !aabbs.is_empty() {
+            bvh.subdivide(0, split);
+        }
+        bvh
+    }
+
+    /// Returns the bounds of all objects
+    pub fn bounds(&self) -> Aabb3f32 {
+        self.nodes[0].bounds
+    }
+
+    /// Updates the boxes of the objects without rebuilding the tree. Queries
+    /// stay correct, but their speed degrades if objects move far.
+    /// Make sure aabbs has the same length as the slice used to build the BVH.
+    pub fn refit(&mut self, aabbs: &[Aabb3f32]) {
+        debug_assert_eq!(aabbs.len(), self.aabbs.len());
+        self.aabbs.copy_from_slice(aabbs);
+
+        // Children are always stored after their parent
+        for i in (0..self.nodes.len()).rev() {
+            let node = self.nodes[i];
+            let bounds = if node.count == 0 {
+                self.nodes[node.first]
+                    .bounds
+                    .merged(&self.nodes[node.first + 1].bounds)
+            } else {
+                self.range_bounds(node.first, node.count)
+            };
+            self.nodes[i].bounds = bounds;
+        }
+    }
+
+    /// Appends the indices of all boxes that intersect the frustum to out
+    pub fn query_frustum(&self, frustum: &Frustum, out: &mut Vec<usize>) {
+        self.query(|bounds| intersects(frustum, bounds), out);
+    }
+
+    /// Appends the indices of all boxes hit by the ray to out
+    pub fn query_ray(&self, ray: &Ray3f32, out: &mut Vec<usize>) {
+        self.query(|bounds| ray.intersect_aabb(bounds).is_some(), out);
+    }
+
+    /// Appends the indices of all boxes for which the predicate returns true
+    /// to out. Subtrees are skipped when it returns false for their bounds,
+    /// so it must also be true for every box that contains an accepted box.
+    pub fn query(&self, predicate: impl Fn(&Aabb3f32) -> bool, out: &mut Vec<usize>) {
+        if self.indices.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            if !predicate(&node.bounds) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.push(node.first + 1);
+                stack.push(node.first);
+                continue;
+            }
+            for &index in &self.indices[node.first..node.first + node.count] {
+                if predicate(&self.aabbs[index]) {
+                    out.push(index);
+                }
+            }
+        }
+    }
+
+    fn range_bounds(&self, first: usize, count: usize) -> Aabb3f32 {
+        let indices = &self.indices[first..first + count];
+        let mut bounds = self.aabbs[indices[0]];
+        for &index in &indices[1..] {
+            bounds.merge(&self.aabbs[index]);
+        }
+        bounds
+    }
+
+    fn subdivide(&mut self, node_index: usize, split: BvhSplit) {
+        let BvhNode { first, count, .. } = self.nodes[node_index];
+        self.nodes[node_index].bounds = self.range_bounds(first, count);
+        if count <= LEAF_SIZE {
+            return;
+        }
+
+        let Some(left_count) = (match split {
+            BvhSplit::Median => self.partition_median(first, count),
+            BvhSplit::Sah => self.partition_sah(first, count),
+        }) else {
+            return;
+        };
+
+        let left = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds: self.nodes[node_index].bounds,
+            first,
+            count: left_count,
+        });
+        self.nodes.push(BvhNode {
+            bounds: self.nodes[node_index].bounds,
+            first: first + left_count,
+            count: count - left_count,
+        });
+        self.nodes[node_index].first = left;
+        self.nodes[node_index].count = 0;
+
+        self.subdivide(left, split);
+        self.subdivide(left + 1, split);
+    }
+
+    /// Returns the bounds of the centers of the boxes in the range
+    fn centroid_bounds(&self, first: usize, count: usize) -> Aabb3f32 {
+        let indices = &self.indices[first..first + count];
+        let first_center = self.aabbs[indices[0]].center();
+        let mut bounds = Aabb3f32::new(first_center, first_center);
+        for &index in &indices[1..] {
+            let center = self.aabbs[index].center();
+            bounds.min = bounds.min.min(center);
+            bounds.max = bounds.max.max(center);
+        }
+        bounds
+    }
+
+    /// Splits the range in half along the longest axis of the centers,
+    /// returns the size of the left half
+    fn partition_median(&mut self, first: usize, count: usize) -> Option<usize> {
+        let centroid_bounds = self.centroid_bounds(first, count);
+        let axis = longest_axis(&centroid_bounds);
+        let aabbs = &self.aabbs;
+        let mid = count / 2;
+        self.indices[first..first + count].select_nth_unstable_by(mid, |&a, &b| {
+            axis_value(aabbs[a].center(), axis).total_cmp(&axis_value(aabbs[b].center(), axis))
+        });
+        Some(mid)
+    }
+
+    /// Splits the range at the binned SAH optimum, returns the size of the
+    /// left half or None if keeping the range as a leaf is cheaper
+    fn partition_sah(&mut self, first: usize, count: usize) -> Option<usize> {
+        let centroid_bounds = self.centroid_bounds(first, count);
+        let mut best: Option<(f32, usize, f32)> = None;
+
+        for axis in 0..3 {
+            let min = axis_value(centroid_bounds.min, axis);
+            let max = axis_value(centroid_bounds.max, axis);
+            if max <= min {
+                continue;
+            }
+            let scale = SAH_BINS as f32 / (max - min);
+            let bin_of = |aabb: &Aabb3f32| {
+                (((axis_value(aabb.center(), axis) - min) * scale) as usize).min(SAH_BINS - 1)
+            };
+
+            let mut bins: [Option<(Aabb3f32, usize)>; SAH_BINS] = [None; SAH_BINS];
+            for &index in &self.indices[first..first + count] {
+                let aabb = &self.aabbs[index];
+                let bin = &mut bins[bin_of(aabb)];
+                *bin = Some(match *bin {
+                    Some((bounds, n)) => (bounds.merged(aabb), n + 1),
+                    None => (*aabb, 1),
+                });
+            }
+
+            // Sweep from the right to get the cost of every right side
+            let mut right_costs = [0.0; SAH_BINS];
+            let mut accumulated: Option<(Aabb3f32, usize)> = None;
+            for i in (1..SAH_BINS).rev() {
+                accumulated = merge_bins(accumulated, bins[i]);
+                right_costs[i] = accumulated.map_or(0.0, |(b, n)| surface_area(&b) * n as f32);
+            }
+            // Then sweep from the left and evaluate each plane between bins
+            let mut accumulated: Option<(Aabb3f32, usize)> = None;
+            for i in 0..SAH_BINS - 1 {
+                accumulated = merge_bins(accumulated, bins[i]);
+                let Some((bounds, n)) = accumulated else {
+                    continue;
+                };
+                let cost = surface_area(&bounds) * n as f32 + right_costs[i + 1];
+                if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                    let plane = min + (i + 1) as f32 / scale;
+                    best = Some((cost, axis, plane));
+                }
+            }
+        }
+
+        let (cost, axis, plane) = best?;
+        let leaf_cost = surface_area(&self.range_bounds(first, count)) * count as f32;
+        if cost >= leaf_cost && count <= 2 * LEAF_SIZE {
+            return None;
+        }
+
+        // Partition the range in place around the plane
+        let aabbs = &self.aabbs;
+        let indices = &mut self.indices[first..first + count];
+        let mut left_count = 0;
+        for i in 0..count {
+            if axis_value(aabbs[indices[i]].center(), axis) < plane {
+                indices.swap(i, left_count);
+                left_count += 1;
+            }
+        }
+        if left_count == 0 || left_count == count {
+            return self.partition_median(first, count);
+        }
+        Some(left_count)
+    }
+}
+
+fn merge_bins(
+    a: Option<(Aabb3f32, usize)>,
+    b: Option<(Aabb3f32, usize)>,
+) -> Option<(Aabb3f32, usize)> {
+    match (a, b) {
+        (Some((a_bounds, a_count)), Some((b_bounds, b_count))) => {
+            Some((a_bounds.merged(&b_bounds), a_count + b_count))
+        }
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn surface_area(aabb: &Aabb3f32) -> f32 {
+    let size = aabb.max - aabb.min;
+    2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+}
+
+fn longest_axis(aabb: &Aabb3f32) -> usize {
+    let size = aabb.max - aabb.min;
+    if size.x >= size.y && size.x >= size.z {
+        0
+    } else if size.y >= size.z {
+        1
+    } else {
+        2
+    }
+}
+
+fn axis_value(v: Vec3f32, axis: usize) -> f32 {
+    match axis {
+        0 => v.x,
+        1 => v.y,
+        _ => v.z,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geometry::intersect::Intersects, mat::mat4f32::Mat4f32};
+
+    /// A grid of small boxes in the xz plane
+    fn grid() -> Vec<Aabb3f32> {
+        let half = Vec3f32::new(0.25, 0.25, 0.25);
+        let mut aabbs = Vec::new();
+        for x in 0..20 {
+            for z in 0..20 {
+                let center = Vec3f32::new(x as f32, 0.0, z as f32);
+                aabbs.push(Aabb3f32::new(center - half, center + half));
+            }
+        }
+        aabbs
+    }
+
+    fn brute_force(aabbs: &[Aabb3f32], predicate: impl Fn(&Aabb3f32) -> bool) -> Vec<usize> {
+        (0..aabbs.len()).filter(|&i| predicate(&aabbs[i])).collect()
+    }
+
+    #[test]
+    fn test_query_matches_brute_force() {
+        let aabbs = grid();
+        let region = Aabb3f32::new(Vec3f32::new(3.5, -1.0, 2.5), Vec3f32::new(7.2, 1.0, 9.9));
+        let expected = brute_force(&aabbs, |aabb| aabb.intersects(&region));
+
+        for split in [BvhSplit::Median, BvhSplit::Sah] {
+            let bvh = Bvh::build(&aabbs, split);
+            let mut result = Vec::new();
+            bvh.query(|aabb| aabb.intersects(&region), &mut result);
+            result.sort();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_query_ray() {
+        let aabbs = grid();
+        let bvh = Bvh::build(&aabbs, BvhSplit::Sah);
+        let ray = Ray3f32::new(Vec3f32::new(-5.0, 0.0, 3.0), Vec3f32::new(1.0, 0.0, 0.0));
+        let mut result = Vec::new();
+        bvh.query_ray(&ray, &mut result);
+        result.sort();
+        assert_eq!(
+            result,
+            brute_force(&aabbs, |aabb| ray.intersect_aabb(aabb).is_some())
+        );
+        assert_eq!(result.len(), 20);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_query_frustum() {
+        // Perspective frustum at (10, 0, 25) looking down -z, near 1 and far 10
+        let (near, far) = (1.0, 10.0);
+        let m = Mat4f32 { values: [
+            1.0, 0.0, 0.0, -10.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, far / (near - far), near * far / (near - far) - 25.0 * far / (near - far),
+            0.0, 0.0, -1.0, 25.0,
+        ]};
+        let frustum = Frustum::from_view_proj(&m).unwrap();
+        let aabbs = grid();
+        let bvh = Bvh::build(&aabbs, BvhSplit::Sah);
+        let mut result = Vec::new();
+        bvh.query_frustum(&frustum, &mut result);
+        result.sort();
+        let expected = brute_force(&aabbs, |aabb| frustum.intersects(aabb));
+        assert!(!expected.is_empty() && expected.len() < aabbs.len());
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_refit() {
+        let mut aabbs = grid();
+        let mut bvh = Bvh::build(&aabbs, BvhSplit::Median);
+        let offset = Vec3f32::new(0.0, 100.0, 0.0);
+        aabbs[7] = Aabb3f32::new(aabbs[7].min + offset, aabbs[7].max + offset);
+        bvh.refit(&aabbs);
+
+        assert!((100.2499..100.2501).contains(&bvh.bounds().max.y));
+        let mut result = Vec::new();
+        let ray = Ray3f32::new(Vec3f32::new(-5.0, 100.0, 7.0), Vec3f32::new(1.0, 0.0, 0.0));
+        bvh.query_ray(&ray, &mut result);
+        assert_eq!(result, vec![7]);
+    }
+}
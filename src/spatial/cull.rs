@@ -0,0 +1,164 @@
+use crate::{
+    geometry::{aabb3f32::Aabb3f32, frustum::Frustum},
+    vec::vec3::Vec3f32,
+};
+
+/// The frustum planes split into one array per component, so the inner
+/// culling loops only touch a few cache lines and can be vectorized
+struct CullPlanes {
+    x: [f32; 6],
+    y: [f32; 6],
+    z: [f32; 6],
+    d: [f32; 6],
+    /// Bounds of the frustum corners, see Intersects<Aabb3f32> for Frustum
+    bounds: Aabb3f32,
+}
+
+impl CullPlanes {
+    fn new(frustum: &Frustum) -> Self {
+        let p = &frustum.planes;
+        Self {
+            x: std::array::from_fn(|i| p[i].normal.x),
+            y: std::array::from_fn(|i| p[i].normal.y),
+            z: std::array::from_fn(|i| p[i].normal.z),
+            d: std::array::from_fn(|i| p[i].d),
+            bounds: Aabb3f32::from_points(&frustum.corners),
+        }
+    }
+
+    #[inline]
+    fn box_visible(&self, center: Vec3f32, extents: Vec3f32) -> bool {
+        let mut inside = true;
+        for i in 0..6 {
+            let distance =
+                self.x[i] * center.x + self.y[i] * center.y + self.z[i] * center.z + self.d[i];
+            let radius = self.x[i].abs() * extents.x
+                + self.y[i].abs() * extents.y
+                + self.z[i].abs() * extents.z;
+            inside &= distance >= -radius;
+        }
+        let b = &self.bounds;
+        inside
+            && (center.x - extents.x <= b.max.x)
+            && (center.x + extents.x >= b.min.x)
+            && (center.y - extents.y <= b.max.y)
+            && (center.y + extents.y >= b.min.y)
+            && (center.z - extents.z <= b.max.z)
+            && (center.z + extents.z >= b.min.z)
+    }
+
+    #[inline]
+    fn sphere_visible(&self, center: Vec3f32, radius: f32) -> bool {
+        let mut inside = true;
+        for i in 0..6 {
+            let distance =
+                self.x[i] * center.x + self.y[i] * center.y + self.z[i] * center.z + self.d[i];
+            inside &= distance >= -radius;
+        }
+        inside
+    }
+}
+
+/// Frustum culls a flat array of boxes. visible is cleared and then gets
+/// one entry per box, true if the box intersects the frustum. Gives the
+/// same results as intersects(frustum, aabb), but prepares the planes once
+/// and runs without branches per plane.
+pub fn cull_aabbs(frustum: &Frustum, aabbs: &[Aabb3f32], visible: &mut Vec<bool>) {
+    let planes = CullPlanes::new(frustum);
+    visible.clear();
+    visible.extend(
+        aabbs
+            .iter()
+            .map(|aabb| planes.box_visible(aabb.center(), aabb.half_extents())),
+    );
+}
+
+/// Same as cull_aabbs for boxes stored as separate arrays of centers and
+/// half extents (structure of arrays).
+/// Make sure both slices have the same length.
+pub fn cull_boxes(
+    frustum: &Frustum,
+    centers: &[Vec3f32],
+    half_extents: &[Vec3f32],
+    visible: &mut Vec<bool>,
+) {
+    let planes = CullPlanes::new(frustum);
+    visible.clear();
+    visible.extend(
+        centers
+            .iter()
+            .zip(half_extents)
+            .map(|(&center, &extents)| planes.box_visible(center, extents)),
+    );
+}
+
+/// Frustum culls spheres stored as separate arrays of centers and radii.
+/// Gives the same results as intersects(frustum, sphere), see cull_aabbs.
+/// Make sure both slices have the same length.
+pub fn cull_spheres(
+    frustum: &Frustum,
+    centers: &[Vec3f32],
+    radii: &[f32],
+    visible: &mut Vec<bool>,
+) {
+    let planes = CullPlanes::new(frustum);
+    visible.clear();
+    visible.extend(
+        centers
+            .iter()
+            .zip(radii)
+            .map(|(&center, &radius)| planes.sphere_visible(center, radius)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geometry::{intersect::intersects, sphere::Sphere},
+        mat::mat4f32::Mat4f32,
+    };
+
+    #[test]
+    fn test_matches_intersects() {
+        let proj = Mat4f32::perspective(1.2, 1.5, 1.0, 10.0);
+        let view = Mat4f32::look_at(
+            Vec3f32::new(1.0, 2.0, 3.0),
+            Vec3f32::new(0.0, 0.0, -4.0),
+            Vec3f32::new(0.0, 1.0, 0.0),
+        );
+        let frustum = Frustum::from_view_proj(&(proj * view)).unwrap();
+
+        let mut aabbs = Vec::new();
+        for x in -6..=6 {
+            for y in -6..=6 {
+                for z in -8..=4 {
+                    let center = Vec3f32::new(x as f32 * 1.7, y as f32 * 1.3, z as f32 * 1.9);
+                    let extents =
+                        Vec3f32::new(0.5 + (x & 1) as f32, 0.3, 0.8 + (z & 1) as f32 * 4.0);
+                    aabbs.push(Aabb3f32::new(center - extents, center + extents));
+                }
+            }
+        }
+
+        let mut visible = vec![true; 3];
+        cull_aabbs(&frustum, &aabbs, &mut visible);
+        assert_eq!(visible.len(), aabbs.len());
+        assert!(visible.iter().any(|&v| v) && visible.iter().any(|&v| !v));
+        for (aabb, &v) in aabbs.iter().zip(&visible) {
+            assert_eq!(v, intersects(&frustum, aabb));
+        }
+
+        let centers: Vec<Vec3f32> = aabbs.iter().map(|a| a.center()).collect();
+        let extents: Vec<Vec3f32> = aabbs.iter().map(|a| a.half_extents()).collect();
+        let mut soa_visible = Vec::new();
+        cull_boxes(&frustum, &centers, &extents, &mut soa_visible);
+        assert_eq!(soa_visible, visible);
+
+        let radii: Vec<f32> = extents.iter().map(|e| e.y).collect();
+        cull_spheres(&frustum, &centers, &radii, &mut visible);
+        for ((&center, &radius), &v) in centers.iter().zip(&radii).zip(&visible) {
+            assert_eq!(v, intersects(&frustum, &Sphere::new(center, radius)));
+        }
+    }
+}
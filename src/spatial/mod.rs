@@ -0,0 +1,3 @@
+pub mod bvh;
+pub mod cull;
+pub mod morton;
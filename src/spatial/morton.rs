@@ -0,0 +1,130 @@
+use crate::{
+    geometry::aabb3f32::Aabb3f32,
+    vec::{vec2::Vec2f32, vec3::Vec3f32},
+};
+
+/// Spreads the lower 32 bits of v so that there is one zero bit between each bit
+fn spread_2(v: u32) -> u64 {
+    let mut v = v as u64;
+    v = (v | (v << 16)) & 0x0000_ffff_0000_ffff;
+    v = (v | (v << 8)) & 0x00ff_00ff_00ff_00ff;
+    v = (v | (v << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    v = (v | (v << 1)) & 0x5555_5555_5555_5555;
+    v
+}
+
+/// Inverse of spread_2
+fn compact_2(v: u64) -> u32 {
+    let mut v = v & 0x5555_5555_5555_5555;
+    v = (v | (v >> 1)) & 0x3333_3333_3333_3333;
+    v = (v | (v >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    v = (v | (v >> 4)) & 0x00ff_00ff_00ff_00ff;
+    v = (v | (v >> 8)) & 0x0000_ffff_0000_ffff;
+    v = (v | (v >> 16)) & 0x0000_0000_ffff_ffff;
+    v as u32
+}
+
+/// Spreads the lower 21 bits of v so that there are two zero bits between each bit
+fn spread_3(v: u32) -> u64 {
+    let mut v = (v & 0x1f_ffff) as u64;
+    v = (v | (v << 32)) & 0x001f_0000_0000_ffff;
+    v = (v | (v << 16)) & 0x001f_0000_ff00_00ff;
+    v = (v | (v << 8)) & 0x100f_00f0_0f00_f00f;
+    v = (v | (v << 4)) & 0x10c3_0c30_c30c_30c3;
+    v = (v | (v << 2)) & 0x1249_2492_4924_9249;
+    v
+}
+
+/// Inverse of spread_3
+fn compact_3(v: u64) -> u32 {
+    let mut v = v & 0x1249_2492_4924_9249;
+    v = (v | (v >> 2)) & 0x10c3_0c30_c30c_30c3;
+    v = (v | (v >> 4)) & 0x100f_00f0_0f00_f00f;
+    v = (v | (v >> 8)) & 0x001f_0000_ff00_00ff;
+    v = (v | (v >> 16)) & 0x001f_0000_0000_ffff;
+    v = (v | (v >> 32)) & 0x0000_0000_001f_ffff;
+    v as u32
+}
+
+/// Interleaves the bits of x and y, x ends up in the lowest bit
+pub fn encode2(x: u32, y: u32) -> u64 {
+    spread_2(x) | (spread_2(y) << 1)
+}
+
+pub fn decode2(code: u64) -> (u32, u32) {
+    (compact_2(code), compact_2(code >> 1))
+}
+
+/// Interleaves the lower 21 bits of x, y and z, x ends up in the lowest bit
+pub fn encode3(x: u32, y: u32, z: u32) -> u64 {
+    spread_3(x) | (spread_3(y) << 1) | (spread_3(z) << 2)
+}
+
+pub fn decode3(code: u64) -> (u32, u32, u32) {
+    (compact_3(code), compact_3(code >> 1), compact_3(code >> 2))
+}
+
+/// Maps v from min..max to an integer in 0..2^bits, clamping values outside
+fn quantize(v: f32, min: f32, max: f32, bits: u32) -> u32 {
+    let cells = (1u64 << bits) as f32;
+    let t = if max > min {
+        (v - min) / (max - min)
+    } else {
+        0.0
+    };
+    ((t * cells) as u64).min((1u64 << bits) - 1) as u32
+}
+
+/// Returns the Morton code of p quantized to bits bits per axis inside
+/// the rectangle min..max. Make sure bits is at most 32.
+pub fn morton2(p: Vec2f32, min: Vec2f32, max: Vec2f32, bits: u32) -> u64 {
+    debug_assert!(bits <= 32);
+    encode2(
+        quantize(p.x, min.x, max.x, bits),
+        quantize(p.y, min.y, max.y, bits),
+    )
+}
+
+/// Returns the Morton code of p quantized to bits bits per axis inside
+/// bounds. Make sure bits is at most 21.
+pub fn morton3(p: Vec3f32, bounds: &Aabb3f32, bits: u32) -> u64 {
+    debug_assert!(bits <= 21);
+    encode3(
+        quantize(p.x, bounds.min.x, bounds.max.x, bits),
+        quantize(p.y, bounds.min.y, bounds.max.y, bits),
+        quantize(p.z, bounds.min.z, bounds.max.z, bits),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(encode2(0b11, 0b01), 0b0111);
+        assert_eq!(encode3(0b1, 0b1, 0b0), 0b011);
+        assert_eq!(encode3(0b10, 0, 0), 0b1000);
+
+        for (x, y, z) in [(0, 0, 0), (1, 2, 3), (0x1f_ffff, 12345, 0x15_5555)] {
+            assert_eq!(decode3(encode3(x, y, z)), (x, y, z));
+        }
+        for (x, y) in [(0, 0), (u32::MAX, 7), (123456, 0xdead_beef)] {
+            assert_eq!(decode2(encode2(x, y)), (x, y));
+        }
+    }
+
+    #[test]
+    fn test_morton3() {
+        let bounds = Aabb3f32::new(Vec3f32::new(-1.0, -1.0, -1.0), Vec3f32::new(1.0, 1.0, 1.0));
+        assert_eq!(morton3(bounds.min, &bounds, 10), 0);
+        assert_eq!(morton3(bounds.max, &bounds, 10), (1 << 30) - 1);
+        let code = morton3(Vec3f32::new(0.0, -1.0, 1.0), &bounds, 2);
+        assert_eq!(decode3(code), (2, 0, 3));
+
+        let min = Vec2f32::new(0.0, 0.0);
+        let max = Vec2f32::new(4.0, 4.0);
+        assert_eq!(morton2(Vec2f32::new(1.5, 2.5), min, max, 2), encode2(1, 2));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod pose3f32;
+pub mod transform2df32;
+pub mod transform3df32;
+pub mod transform_error;
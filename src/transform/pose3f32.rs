@@ -0,0 +1,139 @@
+use crate::{
+    mat::mat4f32::Mat4f32, rotor::rot3df32::Rot3Df32, transform::transform3df32::Transform3Df32,
+    vec::vec3::Vec3f32,
+};
+
+/// Rigid transform without scale, as handed back by physics engines and
+/// XR runtimes. Points are rotated and then moved by position.
+#[derive(Debug, Clone, Copy)]
+pub struct Pose3f32 {
+    pub position: Vec3f32,
+    pub rotation: Rot3Df32,
+}
+
+impl Pose3f32 {
+    /// Returns the pose that changes nothing
+    pub fn identity() -> Self {
+        Self::new(Vec3f32::new(0.0, 0.0, 0.0), Rot3Df32::identity())
+    }
+
+    pub fn new(position: Vec3f32, rotation: Rot3Df32) -> Self {
+        Self { position, rotation }
+    }
+
+    /// Rotates v, ignoring position
+    pub fn transform_vector(&self, v: Vec3f32) -> Vec3f32 {
+        self.rotation.rotated_vec(v)
+    }
+
+    pub fn transform_point(&self, p: Vec3f32) -> Vec3f32 {
+        self.rotation.rotated_vec(p) + self.position
+    }
+
+    /// Returns the pose that applies child first and then self, for example
+    /// the world pose of a child given in the local space of self
+    pub fn combined(&self, child: &Pose3f32) -> Self {
+        Self {
+            position: self.transform_point(child.position),
+            rotation: self.rotation.appended(child.rotation),
+        }
+    }
+
+    /// Returns the inverse pose. Unlike for transforms with scale, it
+    /// always exists and is exact.
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.inverted();
+        Self {
+            position: -rotation.rotated_vec(self.position),
+            rotation,
+        }
+    }
+
+    /// Interpolates between self (t = 0) and other (t = 1), linearly for
+    /// the position and with Rot3Df32::nlerp for the rotation
+    pub fn lerp(&self, other: &Pose3f32, t: f32) -> Self {
+        Self {
+            position: self.position + (other.position - self.position) * t,
+            rotation: self.rotation.nlerp(other.rotation, t),
+        }
+    }
+
+    /// Same as lerp, but with Rot3Df32::slerp for the rotation
+    pub fn slerp(&self, other: &Pose3f32, t: f32) -> Self {
+        Self {
+            position: self.position + (other.position - self.position) * t,
+            rotation: self.rotation.slerp(other.rotation, t),
+        }
+    }
+
+    /// Returns the pose as a matrix
+    pub fn to_mat4(&self) -> Mat4f32 {
+        Mat4f32::from_basis(
+            self.transform_vector(Vec3f32::new(1.0, 0.0, 0.0)),
+            self.transform_vector(Vec3f32::new(0.0, 1.0, 0.0)),
+            self.transform_vector(Vec3f32::new(0.0, 0.0, 1.0)),
+            self.position,
+        )
+    }
+
+    /// Returns the view matrix of a camera with this pose, see
+    /// Mat4f32::view_from_pose
+    pub fn view_matrix(&self) -> Mat4f32 {
+        Mat4f32::view_from_pose(self.position, &self.rotation)
+    }
+
+    /// Returns the pose as a transform with a scale of 1
+    pub fn to_transform(&self) -> Transform3Df32 {
+        Transform3Df32::new(self.position, self.rotation, Vec3f32::new(1.0, 1.0, 1.0))
+    }
+}
+
+impl From<Pose3f32> for Transform3Df32 {
+    fn from(pose: Pose3f32) -> Self {
+        pose.to_transform()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose(x: f32, angle: f32) -> Pose3f32 {
+        let mut axis = Vec3f32::new(1.0, 2.0, -0.5);
+        axis.normalize();
+        Pose3f32::new(
+            Vec3f32::new(x, 2.0, -1.0),
+            Rot3Df32::from_axis_angle(axis, angle),
+        )
+    }
+
+    #[test]
+    fn test_combined_and_inverse() {
+        let (parent, child) = (pose(1.0, 0.7), pose(-3.0, -1.2));
+        let p = Vec3f32::new(0.3, -0.8, 2.0);
+
+        let combined = parent.combined(&child);
+        let expected = parent.transform_point(child.transform_point(p));
+        assert!((-0.0001..0.0001).contains(&combined.transform_point(p).distance(expected)));
+        let q = combined.to_mat4().transform_point(p);
+        assert!((-0.0001..0.0001).contains(&q.distance(expected)));
+        let q = Transform3Df32::from(combined).transform_point(p);
+        assert!((-0.0001..0.0001).contains(&q.distance(expected)));
+
+        let back = parent.inverse().transform_point(parent.transform_point(p));
+        assert!((-0.0001..0.0001).contains(&back.distance(p)));
+        let origin = parent.view_matrix().transform_point(parent.position);
+        assert!((-0.0001..0.0001).contains(&origin.magnitude()));
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let (a, b) = (pose(1.0, 0.2), pose(3.0, 1.0));
+        let expected = pose(2.0, 0.6);
+        let p = Vec3f32::new(0.3, -0.8, 2.0);
+        for halfway in [a.lerp(&b, 0.5), a.slerp(&b, 0.5)] {
+            let q = halfway.transform_point(p);
+            assert!((-0.0001..0.0001).contains(&q.distance(expected.transform_point(p))));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    mat::{mat3f32::Mat3f32, mat4f32::Mat4f32},
+    vec::vec2::Vec2f32,
+};
+
+/// 2D transform for sprites and UI elements. Points are scaled and rotated
+/// around the pivot, then the pivot is moved to translation.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform2Df32 {
+    pub translation: Vec2f32,
+    /// Counter-clockwise rotation in radians
+    pub rotation: f32,
+    pub scale: Vec2f32,
+    /// Local point that scaling and rotation happen around
+    pub pivot: Vec2f32,
+}
+
+impl Transform2Df32 {
+    /// Returns the transform that changes nothing
+    pub fn identity() -> Self {
+        Self::new(Vec2f32::new(0.0, 0.0), 0.0, Vec2f32::new(1.0, 1.0))
+    }
+
+    pub fn new(translation: Vec2f32, rotation: f32, scale: Vec2f32) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+            pivot: Vec2f32::new(0.0, 0.0),
+        }
+    }
+
+    pub fn with_pivot(mut self, pivot: Vec2f32) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Rotates and scales v, ignoring translation and pivot
+    pub fn transform_vector(&self, v: Vec2f32) -> Vec2f32 {
+        Vec2f32::new(v.x * self.scale.x, v.y * self.scale.y).rotated(self.rotation)
+    }
+
+    pub fn transform_point(&self, p: Vec2f32) -> Vec2f32 {
+        self.transform_vector(p - self.pivot) + self.translation
+    }
+
+    /// Returns the transform that applies child first and then self, used
+    /// for parenting. Returns None if self has a non-uniform scale and child
+    /// is rotated, because the result contains shear. Use to_mat3 and matrix
+    /// multiplication in that case.
+    pub fn combined(&self, child: &Transform2Df32) -> Option<Self> {
+        if self.scale.x != self.scale.y && child.rotation != 0.0 {
+            return None;
+        }
+        Some(Self {
+            translation: self.transform_point(child.translation),
+            rotation: self.rotation + child.rotation,
+            scale: Vec2f32::new(self.scale.x * child.scale.x, self.scale.y * child.scale.y),
+            pivot: child.pivot,
+        })
+    }
+
+    /// Returns the inverse transform, or None if the scale is zero or not
+    /// uniform. The inverse of a non-uniform scale followed by a rotation
+    /// can't be expressed as a Transform2Df32, use to_mat3().inverse()
+    /// in that case.
+    pub fn inverse(&self) -> Option<Self> {
+        if self.scale.x == 0.0 || self.scale.x != self.scale.y {
+            return None;
+        }
+        Some(Self {
+            translation: self.pivot,
+            rotation: -self.rotation,
+            scale: Vec2f32::new(1.0 / self.scale.x, 1.0 / self.scale.y),
+            pivot: self.translation,
+        })
+    }
+
+    /// Returns the transform as a matrix acting on homogeneous 2D points (x, y, 1)
+    #[rustfmt::skip]
+    pub fn to_mat3(&self) -> Mat3f32 {
+        let x_axis = self.transform_vector(Vec2f32::new(1.0, 0.0));
+        let y_axis = self.transform_vector(Vec2f32::new(0.0, 1.0));
+        let t = self.translation - self.transform_vector(self.pivot);
+        Mat3f32 { values: [
+            x_axis.x, y_axis.x, t.x,
+            x_axis.y, y_axis.y, t.y,
+            0.0, 0.0, 1.0,
+        ]}
+    }
+
+    /// Returns the transform as a 3D matrix acting in the xy plane,
+    /// z is left unchanged
+    #[rustfmt::skip]
+    pub fn to_mat4(&self) -> Mat4f32 {
+        let m = self.to_mat3().values;
+        Mat4f32 { values: [
+            m[0], m[1], 0.0, m[2],
+            m[3], m[4], 0.0, m[5],
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        ]}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec3::Vec3f32;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn check_vec_equal(a: Vec2f32, b: Vec2f32) -> bool {
+        (a - b).magnitude() < 0.0001
+    }
+
+    #[test]
+    fn test_transform_point() {
+        // A 2x1 sprite rotated around its center
+        let transform =
+            Transform2Df32::new(Vec2f32::new(10.0, 5.0), FRAC_PI_2, Vec2f32::new(2.0, 1.0))
+                .with_pivot(Vec2f32::new(1.0, 0.5));
+        let p = transform.transform_point(Vec2f32::new(2.0, 0.5));
+        assert!(check_vec_equal(p, Vec2f32::new(10.0, 7.0)));
+
+        let m = transform.to_mat3();
+        let q = m.mul_vec(Vec3f32::new(2.0, 0.5, 1.0));
+        assert!(check_vec_equal(Vec2f32::new(q.x, q.y), p));
+        let q = transform
+            .to_mat4()
+            .transform_point(Vec3f32::new(2.0, 0.5, 3.0));
+        assert!(check_vec_equal(Vec2f32::new(q.x, q.y), p));
+        assert!((2.9999..3.0001).contains(&q.z));
+    }
+
+    #[test]
+    fn test_combined_and_inverse() {
+        let parent = Transform2Df32::new(Vec2f32::new(3.0, -1.0), 0.7, Vec2f32::new(2.0, 2.0))
+            .with_pivot(Vec2f32::new(0.5, 0.5));
+        let child = Transform2Df32::new(Vec2f32::new(1.0, 2.0), -0.2, Vec2f32::new(1.0, 3.0))
+            .with_pivot(Vec2f32::new(0.0, 1.0));
+        let p = Vec2f32::new(0.3, -0.8);
+
+        let combined = parent.combined(&child).unwrap();
+        let expected = parent.transform_point(child.transform_point(p));
+        assert!(check_vec_equal(combined.transform_point(p), expected));
+
+        let inverse = parent.inverse().unwrap();
+        assert!(check_vec_equal(
+            inverse.transform_point(parent.transform_point(p)),
+            p
+        ));
+        assert!(Transform2Df32::new(p, 0.0, Vec2f32::new(0.0, 0.0))
+            .inverse()
+            .is_none());
+    }
+
+    #[test]
+    fn test_non_uniform_scale() {
+        let parent = Transform2Df32::new(Vec2f32::new(3.0, -1.0), 0.7, Vec2f32::new(2.0, 0.5));
+        let child = Transform2Df32::new(Vec2f32::new(1.0, 2.0), -0.2, Vec2f32::new(1.0, 3.0));
+        let p = Vec2f32::new(0.3, -0.8);
+        assert!(parent.inverse().is_none());
+        assert!(parent.combined(&child).is_none());
+
+        // Without a rotated child there is no shear
+        let unrotated = Transform2Df32::new(Vec2f32::new(1.0, 2.0), 0.0, Vec2f32::new(1.0, 3.0));
+        let combined = parent.combined(&unrotated).unwrap();
+        let expected = parent.transform_point(unrotated.transform_point(p));
+        assert!(check_vec_equal(combined.transform_point(p), expected));
+
+        // The matrices handle the shear the transforms can't represent
+        let inverse = parent.to_mat3().inverse().unwrap();
+        let q = parent.transform_point(p);
+        let q = inverse.mul_vec(Vec3f32::new(q.x, q.y, 1.0));
+        assert!(check_vec_equal(Vec2f32::new(q.x, q.y), p));
+
+        let combined = parent.to_mat3() * child.to_mat3();
+        let q = combined.mul_vec(Vec3f32::new(p.x, p.y, 1.0));
+        let expected = parent.transform_point(child.transform_point(p));
+        assert!(check_vec_equal(Vec2f32::new(q.x, q.y), expected));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    geometry::plane::Plane, mat::mat4f32::Mat4f32, rotor::rot3df32::Rot3Df32, vec::vec3::Vec3f32,
+};
+use std::f32::consts::PI;
+
+/// 3D transform of an object. Points are scaled, then rotated and then
+/// moved by translation.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform3Df32 {
+    pub translation: Vec3f32,
+    pub rotation: Rot3Df32,
+    pub scale: Vec3f32,
+}
+
+impl Transform3Df32 {
+    /// Returns the transform that changes nothing
+    pub fn identity() -> Self {
+        Self::new(
+            Vec3f32::new(0.0, 0.0, 0.0),
+            Rot3Df32::identity(),
+            Vec3f32::new(1.0, 1.0, 1.0),
+        )
+    }
+
+    pub fn new(translation: Vec3f32, rotation: Rot3Df32, scale: Vec3f32) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    /// Scales and rotates v, ignoring translation
+    pub fn transform_vector(&self, v: Vec3f32) -> Vec3f32 {
+        self.rotation.rotated_vec(Vec3f32::new(
+            v.x * self.scale.x,
+            v.y * self.scale.y,
+            v.z * self.scale.z,
+        ))
+    }
+
+    pub fn transform_point(&self, p: Vec3f32) -> Vec3f32 {
+        self.transform_vector(p) + self.translation
+    }
+
+    /// Returns the transform as a matrix
+    pub fn to_mat4(&self) -> Mat4f32 {
+        Mat4f32::from_basis(
+            self.transform_vector(Vec3f32::new(1.0, 0.0, 0.0)),
+            self.transform_vector(Vec3f32::new(0.0, 1.0, 0.0)),
+            self.transform_vector(Vec3f32::new(0.0, 0.0, 1.0)),
+            self.translation,
+        )
+    }
+
+    /// Returns the mirror image of the transform across the plane, the
+    /// transform of an object in a mirror world. A reflection can't be
+    /// expressed as a rotation, so the scale is negated and the rotation
+    /// turned by 180 degrees around the plane normal instead. The triangle
+    /// winding flips, so swap the front faces when rendering with it.
+    /// Make sure the plane normal is normalized.
+    pub fn reflected(&self, plane: &Plane) -> Self {
+        Self {
+            translation: plane.reflect_point(self.translation),
+            rotation: Rot3Df32::from_axis_angle(plane.normal, PI).appended(self.rotation),
+            scale: -self.scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_point() {
+        let mut axis = Vec3f32::new(1.0, 1.0, 0.0);
+        axis.normalize();
+        let transform = Transform3Df32::new(
+            Vec3f32::new(1.0, 2.0, 3.0),
+            Rot3Df32::from_axis_angle(axis, 0.8),
+            Vec3f32::new(2.0, 1.0, 0.5),
+        );
+        let p = Vec3f32::new(0.5, -1.0, 2.0);
+        let expected = transform.transform_point(p);
+        let q = transform.to_mat4().transform_point(p);
+        assert!((-0.0001..0.0001).contains(&q.distance(expected)));
+        let q = Transform3Df32::identity().transform_point(p);
+        assert!((-0.0001..0.0001).contains(&q.distance(p)));
+    }
+
+    #[test]
+    fn test_reflected() {
+        let mut normal = Vec3f32::new(0.3, 1.0, -0.2);
+        normal.normalize();
+        let plane = Plane::from_point_normal(Vec3f32::new(0.0, -1.0, 0.5), normal);
+        let transform = Transform3Df32::new(
+            Vec3f32::new(1.0, 2.0, 3.0),
+            Rot3Df32::from_axis_angle(Vec3f32::new(0.0, 0.0, 1.0), 0.8),
+            Vec3f32::new(2.0, 1.0, 0.5),
+        );
+        let reflected = transform.reflected(&plane);
+        let mirror = Mat4f32::reflection(&plane);
+
+        for p in [Vec3f32::new(0.5, -1.0, 2.0), Vec3f32::new(-3.0, 0.0, 1.0)] {
+            let expected = plane.reflect_point(transform.transform_point(p));
+            assert!((-0.0001..0.0001).contains(&reflected.transform_point(p).distance(expected)));
+            let q = (mirror * transform.to_mat4()).transform_point(p);
+            assert!((-0.0001..0.0001).contains(&q.distance(expected)));
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+
+/// Why a matrix or rotor can't be used as a transform, see
+/// Mat4f32::validate and Rot3Df32::validate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransformError {
+    /// Contains NaN or infinite values
+    NonFinite,
+    /// The last row is not 0, 0, 0, 1, so the matrix is a projection
+    NotAffine,
+    /// The matrix collapses space onto a plane, line or point (a scale of
+    /// 0), so it can't be inverted and breaks normals
+    Singular,
+    /// The rotor has the given magnitude instead of 1 and would scale
+    /// vectors, Rot3Df32::normalize repairs it
+    NotNormalized { magnitude: f32 },
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransformError::NonFinite => write!(f, "transform contains NaN or infinite values"),
+            TransformError::NotAffine => write!(f, "matrix is projective, not affine"),
+            TransformError::Singular => write!(f, "matrix is singular (scale of 0)"),
+            TransformError::NotNormalized { magnitude } => {
+                write!(f, "rotor has magnitude {magnitude} instead of 1")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransformError {}
@@ -0,0 +1,91 @@
+use crate::vec::vec3::Vec3f32;
+
+/// Bit-exact key for a Vec3f32, usable in HashMap and HashSet for vertex
+/// deduplication. Two vectors are equal if their components have the same
+/// bits, except that -0.0 equals 0.0. Use QuantizedVec3f32 to merge
+/// vertices that are only approximately equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HashableVec3f32 {
+    bits: [u32; 3],
+}
+
+impl HashableVec3f32 {
+    pub fn new(v: Vec3f32) -> Self {
+        // Adding 0.0 turns -0.0 into 0.0 and keeps every other value
+        let bits = |f: f32| (f + 0.0).to_bits();
+        Self {
+            bits: [bits(v.x), bits(v.y), bits(v.z)],
+        }
+    }
+
+    pub fn to_vec3(self) -> Vec3f32 {
+        Vec3f32::new(
+            f32::from_bits(self.bits[0]),
+            f32::from_bits(self.bits[1]),
+            f32::from_bits(self.bits[2]),
+        )
+    }
+}
+
+impl From<Vec3f32> for HashableVec3f32 {
+    fn from(v: Vec3f32) -> Self {
+        Self::new(v)
+    }
+}
+
+/// Key of the grid cell a Vec3f32 falls into, for approximate welding.
+/// Vectors closer than cell_size can still end up in neighbouring cells
+/// when they straddle a cell border. So to weld with a tolerance, use the
+/// tolerance as cell_size, look up the key and its neighbours (see
+/// neighbours) in the map of already welded vertices and compare the
+/// actual distance to the candidates found there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QuantizedVec3f32 {
+    pub cell: [i64; 3],
+}
+
+impl QuantizedVec3f32 {
+    /// Make sure cell_size is greater than 0
+    pub fn new(v: Vec3f32, cell_size: f32) -> Self {
+        let quantize = |f: f32| (f / cell_size).floor() as i64;
+        Self {
+            cell: [quantize(v.x), quantize(v.y), quantize(v.z)],
+        }
+    }
+
+    /// Returns the keys of this cell and the 26 cells around it
+    pub fn neighbours(&self) -> [Self; 27] {
+        std::array::from_fn(|i| {
+            let offset = [i % 3, (i / 3) % 3, i / 9].map(|o| o as i64 - 1);
+            Self {
+                cell: std::array::from_fn(|axis| self.cell[axis] + offset[axis]),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_hashable() {
+        let mut set = HashSet::new();
+        set.insert(HashableVec3f32::new(Vec3f32::new(1.0, 0.0, -2.5)));
+        assert!(set.contains(&Vec3f32::new(1.0, -0.0, -2.5).into()));
+        assert!(!set.contains(&Vec3f32::new(1.0, 0.0, -2.5001).into()));
+
+        let v = HashableVec3f32::new(Vec3f32::new(0.1, 0.2, 0.3)).to_vec3();
+        assert_eq!(v.y.to_bits(), 0.2f32.to_bits());
+    }
+
+    #[test]
+    fn test_quantized() {
+        let a = QuantizedVec3f32::new(Vec3f32::new(0.0999, 1.0, -0.01), 0.1);
+        let b = QuantizedVec3f32::new(Vec3f32::new(0.1001, 1.0, -0.01), 0.1);
+        assert_ne!(a, b);
+        assert!(a.neighbours().contains(&b));
+        assert_eq!(a.cell[2], -1);
+    }
+}
@@ -1 +1,3 @@
+pub mod hashable_vec3;
+pub mod vec2;
 pub mod vec3;
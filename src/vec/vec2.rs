@@ -0,0 +1,218 @@
+use crate::{
+    float,
+    ops::{forward_ref_binop, forward_ref_unop},
+};
+use std::{
+    iter::Sum,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Vec2f32 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2f32 {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn magnitude(&self) -> f32 {
+        float::sqrt(self.x * self.x + self.y * self.y)
+    }
+
+    pub fn magnitude_squared(&self) -> f32 {
+        self.x * self.x + self.y * self.y
+    }
+
+    pub fn normalize(&mut self) {
+        let mag = self.magnitude();
+        self.x /= mag;
+        self.y /= mag;
+    }
+
+    pub fn normalized(&self) -> Self {
+        let mag = self.magnitude();
+        Self {
+            x: self.x / mag,
+            y: self.y / mag,
+        }
+    }
+
+    pub fn dot(&self, v: Self) -> f32 {
+        self.x * v.x + self.y * v.y
+    }
+
+    /// Returns the closest point on a grid with the given cell size.
+    /// Make sure cell_size is greater than 0.
+    pub fn snap_to_grid(&self, cell_size: f32) -> Self {
+        let snap = |f: f32| (f / cell_size).round() * cell_size;
+        Self::new(snap(self.x), snap(self.y))
+    }
+
+    /// Rounds each component down to a multiple of step, which gives the
+    /// min corner of the grid cell self falls into.
+    /// Make sure step is greater than 0.
+    pub fn quantize(&self, step: f32) -> Self {
+        let quantize = |f: f32| (f / step).floor() * step;
+        Self::new(quantize(self.x), quantize(self.y))
+    }
+
+    /// Returns the average of the points, or None if points is empty
+    pub fn average(points: &[Vec2f32]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        Some(points.iter().sum::<Self>() / points.len() as f32)
+    }
+
+    /// Returns the unit vector pointing in the direction of angle (in
+    /// radians, counter-clockwise from the x axis)
+    pub fn from_angle(angle: f32) -> Self {
+        let (sin, cos) = float::sin_cos(angle);
+        Self::new(cos, sin)
+    }
+
+    /// Returns the angle of the vector in radians (-PI..PI), measured
+    /// counter-clockwise from the x axis
+    pub fn angle(&self) -> f32 {
+        float::atan2(self.y, self.x)
+    }
+
+    /// Returns the vector rotated by 90 degrees counter-clockwise
+    pub fn perp(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Returns the scalar 2D cross product (the z component of the 3D cross
+    /// product). It is positive if v is counter-clockwise from self.
+    pub fn cross(&self, v: Self) -> f32 {
+        self.x * v.y - self.y * v.x
+    }
+
+    /// Returns the vector rotated counter-clockwise by angle (in radians)
+    pub fn rotated(&self, angle: f32) -> Self {
+        let (sin, cos) = float::sin_cos(angle);
+        Self::new(cos * self.x - sin * self.y, sin * self.x + cos * self.y)
+    }
+
+    /// Rotates the vector counter-clockwise by angle (in radians)
+    pub fn rotate(&mut self, angle: f32) {
+        *self = self.rotated(angle);
+    }
+}
+
+impl Add for Vec2f32 {
+    type Output = Vec2f32;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec2f32::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2f32 {
+    type Output = Vec2f32;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2f32::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Vec2f32 {
+    type Output = Vec2f32;
+
+    fn neg(self) -> Self::Output {
+        Vec2f32::new(-self.x, -self.y)
+    }
+}
+
+impl Div<f32> for Vec2f32 {
+    type Output = Vec2f32;
+
+    fn div(mut self, rhs: f32) -> Self::Output {
+        self.x /= rhs;
+        self.y /= rhs;
+        self
+    }
+}
+
+impl Mul<f32> for Vec2f32 {
+    type Output = Vec2f32;
+
+    fn mul(mut self, rhs: f32) -> Self::Output {
+        self.x *= rhs;
+        self.y *= rhs;
+        self
+    }
+}
+
+impl Sum for Vec2f32 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Vec2f32::new(0.0, 0.0), |sum, v| sum + v)
+    }
+}
+
+impl<'a> Sum<&'a Vec2f32> for Vec2f32 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl IntoIterator for Vec2f32 {
+    type Item = f32;
+    type IntoIter = std::array::IntoIter<f32, 2>;
+
+    /// Iterates over the components in the order x, y
+    fn into_iter(self) -> Self::IntoIter {
+        [self.x, self.y].into_iter()
+    }
+}
+
+impl FromIterator<f32> for Vec2f32 {
+    /// Takes the first 2 values as x, y. Panics if the iterator
+    /// yields less than 2 values.
+    fn from_iter<I: IntoIterator<Item = f32>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        Self {
+            x: iter.next().expect("not enough components"),
+            y: iter.next().expect("not enough components"),
+        }
+    }
+}
+
+forward_ref_binop!(impl Add, add for Vec2f32, Vec2f32);
+forward_ref_binop!(impl Sub, sub for Vec2f32, Vec2f32);
+forward_ref_binop!(impl Mul, mul for Vec2f32, f32);
+forward_ref_binop!(impl Div, div for Vec2f32, f32);
+forward_ref_unop!(impl Neg, neg for Vec2f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized() {
+        let v = Vec2f32::new(3.0, -4.0);
+        assert!((4.9999..5.0001).contains(&v.magnitude()));
+        let n = v.normalized();
+        assert!((0.9999..1.0001).contains(&n.magnitude()));
+        assert!((-0.0001..0.0001).contains(&(n.x - 0.6)));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let v = Vec2f32::new(2.0, 1.0);
+        let p = v.perp();
+        assert!((-0.0001..0.0001).contains(&v.dot(p)));
+        assert!(v.cross(p) > 0.0);
+        assert!(p.cross(v) < 0.0);
+
+        let r = v.rotated(std::f32::consts::FRAC_PI_2);
+        assert!((-0.0001..0.0001).contains(&(r - p).magnitude()));
+        let d = Vec2f32::from_angle(v.angle()) * v.magnitude();
+        assert!((-0.0001..0.0001).contains(&(d - v).magnitude()));
+        let pi = std::f32::consts::PI;
+        assert!((pi - 0.0001..pi + 0.0001).contains(&Vec2f32::new(-1.0, 0.0).angle()));
+    }
+}
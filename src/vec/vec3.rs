@@ -1,4 +1,11 @@
-use std::ops::{Add, Div, Mul};
+use crate::{
+    float, math,
+    ops::{forward_ref_binop, forward_ref_unop},
+};
+use std::{
+    iter::Sum,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Vec3f32 {
@@ -13,7 +20,16 @@ impl Vec3f32 {
     }
 
     pub fn magnitude(&self) -> f32 {
-        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+        float::sqrt(self.x * self.x + self.y * self.y + self.z * self.z)
+    }
+
+    pub fn magnitude_squared(&self) -> f32 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    /// Returns the distance between the points self and v
+    pub fn distance(&self, v: Self) -> f32 {
+        (*self - v).magnitude()
     }
 
     pub fn normalize(&mut self) {
@@ -36,6 +52,39 @@ impl Vec3f32 {
         self.x * v.x + self.y * v.y + self.z * v.z
     }
 
+    /// Returns the closest point on a grid with the given cell size,
+    /// for example to snap gizmo movement. Make sure cell_size is greater than 0.
+    pub fn snap_to_grid(&self, cell_size: f32) -> Self {
+        let snap = |f: f32| (f / cell_size).round() * cell_size;
+        Self::new(snap(self.x), snap(self.y), snap(self.z))
+    }
+
+    /// Rounds each component down to a multiple of step, which gives the
+    /// min corner of the grid cell (or voxel) self falls into.
+    /// Make sure step is greater than 0.
+    pub fn quantize(&self, step: f32) -> Self {
+        let quantize = |f: f32| (f / step).floor() * step;
+        Self::new(quantize(self.x), quantize(self.y), quantize(self.z))
+    }
+
+    /// Returns the average of the points, or None if points is empty
+    pub fn average(points: &[Vec3f32]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        Some(points.iter().sum::<Self>() / points.len() as f32)
+    }
+
+    /// Returns the component-wise minimum of self and v
+    pub fn min(&self, v: Self) -> Self {
+        Self::new(self.x.min(v.x), self.y.min(v.y), self.z.min(v.z))
+    }
+
+    /// Returns the component-wise maximum of self and v
+    pub fn max(&self, v: Self) -> Self {
+        Self::new(self.x.max(v.x), self.y.max(v.y), self.z.max(v.z))
+    }
+
     pub fn cross(&self, v: Self) -> Vec3f32 {
         Vec3f32 {
             x: self.y * v.z - self.z * v.y,
@@ -44,6 +93,13 @@ impl Vec3f32 {
         }
     }
 
+    /// Returns the scalar triple product a . (b x c), the signed volume of the
+    /// parallelepiped spanned by a, b and c. It is positive if a, b, c form
+    /// a right-handed system and 0 if they are coplanar.
+    pub fn triple(a: Self, b: Self, c: Self) -> f32 {
+        a.dot(b.cross(c))
+    }
+
     /// Generates an arbitrary unit (normalized) vector that is perpendicular to self.
     /// Make sure self is not 0
     pub fn perpendicular(&self) -> Self {
@@ -92,6 +148,21 @@ impl Vec3f32 {
         result.normalize();
         result
     }
+
+    /// Returns two unit vectors that form a right-handed orthonormal basis
+    /// (t, b, self) together with self, using the branchless method by
+    /// Duff et al. The basis changes continuously with self, except at the
+    /// z = 0 sign flip.
+    /// Make sure self is normalized.
+    pub fn orthonormal_basis(&self) -> (Vec3f32, Vec3f32) {
+        let sign = math::sign_not_zero(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Vec3f32::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Vec3f32::new(b, sign + self.y * self.y * a, -self.y),
+        )
+    }
 }
 
 impl Add for Vec3f32 {
@@ -102,6 +173,22 @@ impl Add for Vec3f32 {
     }
 }
 
+impl Sub for Vec3f32 {
+    type Output = Vec3f32;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3f32::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Neg for Vec3f32 {
+    type Output = Vec3f32;
+
+    fn neg(self) -> Self::Output {
+        Vec3f32::new(-self.x, -self.y, -self.z)
+    }
+}
+
 impl Div<f32> for Vec3f32 {
     type Output = Vec3f32;
 
@@ -124,10 +211,110 @@ impl Mul<f32> for Vec3f32 {
     }
 }
 
+impl Sum for Vec3f32 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Vec3f32::new(0.0, 0.0, 0.0), |sum, v| sum + v)
+    }
+}
+
+impl<'a> Sum<&'a Vec3f32> for Vec3f32 {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl IntoIterator for Vec3f32 {
+    type Item = f32;
+    type IntoIter = std::array::IntoIter<f32, 3>;
+
+    /// Iterates over the components in the order x, y, z
+    fn into_iter(self) -> Self::IntoIter {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
+impl FromIterator<f32> for Vec3f32 {
+    /// Takes the first 3 values as x, y, z. Panics if the iterator
+    /// yields less than 3 values.
+    fn from_iter<I: IntoIterator<Item = f32>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        Self {
+            x: iter.next().expect("not enough components"),
+            y: iter.next().expect("not enough components"),
+            z: iter.next().expect("not enough components"),
+        }
+    }
+}
+
+forward_ref_binop!(impl Add, add for Vec3f32, Vec3f32);
+forward_ref_binop!(impl Sub, sub for Vec3f32, Vec3f32);
+forward_ref_binop!(impl Mul, mul for Vec3f32, f32);
+forward_ref_binop!(impl Div, div for Vec3f32, f32);
+forward_ref_unop!(impl Neg, neg for Vec3f32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_snapping() {
+        let v = Vec3f32::new(0.74, -0.74, 1.26);
+        let snapped = v.snap_to_grid(0.5);
+        assert!((0.4999..0.5001).contains(&snapped.x));
+        assert!((-0.5001..-0.4999).contains(&snapped.y));
+        assert!((1.4999..1.5001).contains(&snapped.z));
+
+        let quantized = v.quantize(0.5);
+        assert!((0.4999..0.5001).contains(&quantized.x));
+        assert!((-1.0001..-0.9999).contains(&quantized.y));
+        assert!((0.9999..1.0001).contains(&quantized.z));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let v = Vec3f32::new(1.0, 2.0, 3.0);
+        assert_eq!(v.into_iter().collect::<Vec<f32>>(), vec![1.0, 2.0, 3.0]);
+        let doubled: Vec3f32 = v.into_iter().map(|c| c * 2.0).collect();
+        assert!((5.9999..6.0001).contains(&doubled.z));
+
+        let points = [v, Vec3f32::new(3.0, 0.0, -1.0)];
+        let sum: Vec3f32 = points.iter().copied().sum();
+        assert!((3.9999..4.0001).contains(&sum.x));
+        let average = Vec3f32::average(&points).unwrap();
+        assert!((0.9999..1.0001).contains(&average.y));
+        assert!(Vec3f32::average(&[]).is_none());
+    }
+
+    #[test]
+    fn test_triple() {
+        let x = Vec3f32::new(1.0, 0.0, 0.0);
+        let y = Vec3f32::new(0.0, 2.0, 0.0);
+        let z = Vec3f32::new(0.0, 0.0, 3.0);
+        assert!((5.9999..6.0001).contains(&Vec3f32::triple(x, y, z)));
+        assert!((-6.0001..-5.9999).contains(&Vec3f32::triple(y, x, z)));
+        assert!((-0.0001..0.0001).contains(&Vec3f32::triple(x, y, x + y)));
+    }
+
+    #[test]
+    fn test_orthonormal_basis() {
+        for mut n in [
+            Vec3f32::new(0.0, 0.0, 1.0),
+            Vec3f32::new(0.0, 0.0, -1.0),
+            Vec3f32::new(1.0, -2.0, 0.5),
+            Vec3f32::new(-0.3, 0.1, -4.0),
+        ] {
+            n.normalize();
+            let (t, b) = n.orthonormal_basis();
+            assert!((0.9999..1.0001).contains(&t.magnitude()));
+            assert!((0.9999..1.0001).contains(&b.magnitude()));
+            assert!((-0.0001..0.0001).contains(&t.dot(n)));
+            assert!((-0.0001..0.0001).contains(&b.dot(n)));
+            assert!((-0.0001..0.0001).contains(&t.dot(b)));
+            // Right-handed
+            assert!((0.9999..1.0001).contains(&t.cross(b).dot(n)));
+        }
+    }
+
     #[test]
     fn test_perpendicular() {
         let vec = Vec3f32::new(0.0, 1.0, 0.0);
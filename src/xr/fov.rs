@@ -0,0 +1,66 @@
+use crate::{float, mat::mat4f32::Mat4f32, render::conventions::Conventions};
+
+/// Same layout as XrFovf, the angles (in radians) of the four sides of a
+/// view frustum from the view direction. angle_left and angle_down are
+/// usually negative, and the frustum is usually asymmetric.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct XrFov {
+    pub angle_left: f32,
+    pub angle_right: f32,
+    pub angle_up: f32,
+    pub angle_down: f32,
+}
+
+impl XrFov {
+    /// Returns the projection matrix of the view for the graphics backend
+    /// described by conventions, for example Conventions::VULKAN for a
+    /// Vulkan swapchain
+    pub fn projection(&self, conventions: &Conventions, near: f32, far: f32) -> Mat4f32 {
+        conventions.frustum(
+            float::tan(self.angle_left) * near,
+            float::tan(self.angle_right) * near,
+            float::tan(self.angle_down) * near,
+            float::tan(self.angle_up) * near,
+            near,
+            far,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::vec3::Vec3f32;
+
+    #[test]
+    fn test_projection() {
+        let fov = XrFov {
+            angle_left: -0.9,
+            angle_right: 0.7,
+            angle_up: 0.8,
+            angle_down: -0.85,
+        };
+        let proj = fov.projection(&Conventions::WGPU, 0.1, 100.0);
+
+        // Directions along the edges of the fov end up on the edges of NDC
+        let (sin, cos) = (fov.angle_right.sin(), fov.angle_right.cos());
+        let ndc = proj.transform_point_projective(Vec3f32::new(sin, 0.0, -cos));
+        assert!((0.9999..1.0001).contains(&ndc.x));
+        let (sin, cos) = (fov.angle_down.sin(), fov.angle_down.cos());
+        let ndc = proj.transform_point_projective(Vec3f32::new(0.0, sin, -cos));
+        assert!((-1.0001..-0.9999).contains(&ndc.y));
+
+        let symmetric = XrFov {
+            angle_left: -0.6,
+            angle_right: 0.6,
+            angle_up: 0.6,
+            angle_down: -0.6,
+        };
+        let proj = symmetric.projection(&Conventions::WGPU, 0.1, 100.0);
+        let expected = Mat4f32::perspective(1.2, 1.0, 0.1, 100.0);
+        for (a, b) in proj.values.iter().zip(expected.values) {
+            assert!((-0.0001..0.0001).contains(&(a - b)));
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod fov;
+pub mod pose;
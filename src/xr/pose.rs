@@ -0,0 +1,155 @@
+use crate::{
+    mat::mat4f32::Mat4f32, rotor::rot3df32::Rot3Df32, transform::pose3f32::Pose3f32,
+    vec::vec3::Vec3f32,
+};
+
+/// Same layout as XrQuaternionf, a unit quaternion with w as the scalar part
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct XrQuaternion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+/// Same layout as XrVector3f
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct XrVector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// Same layout as XrPosef. OpenXR spaces are right-handed with +y up and
+/// the views looking down -z, like the defaults of this crate, so no axes
+/// have to be swapped.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct XrPose {
+    pub orientation: XrQuaternion,
+    pub position: XrVector3,
+}
+
+impl XrPose {
+    /// Returns the matrix that moves points from the local space of the pose
+    /// (for example a controller grip) into the space it is given in
+    pub fn to_mat4(&self) -> Mat4f32 {
+        Pose3f32::from(*self).to_mat4()
+    }
+
+    /// Returns the view matrix for an eye at this pose, as located by
+    /// xrLocateViews
+    pub fn view_matrix(&self) -> Mat4f32 {
+        Pose3f32::from(*self).view_matrix()
+    }
+
+    /// Returns the pose of a rigid transformation matrix.
+    /// Make sure the matrix only rotates and translates.
+    pub fn from_mat4(m: &Mat4f32) -> Self {
+        let v = &m.values;
+        let rotation = Rot3Df32::from_basis(
+            Vec3f32::new(v[0], v[4], v[8]),
+            Vec3f32::new(v[1], v[5], v[9]),
+            Vec3f32::new(v[2], v[6], v[10]),
+        );
+        Pose3f32::new(Vec3f32::new(v[3], v[7], v[11]), rotation).into()
+    }
+}
+
+impl From<Rot3Df32> for XrQuaternion {
+    fn from(r: Rot3Df32) -> Self {
+        // The quaternion vector part is the negated bivector
+        Self {
+            x: -r.yz,
+            y: -r.zx,
+            z: -r.xy,
+            w: r.s,
+        }
+    }
+}
+
+impl From<XrQuaternion> for Rot3Df32 {
+    fn from(q: XrQuaternion) -> Self {
+        Rot3Df32 {
+            s: q.w,
+            xy: -q.z,
+            yz: -q.x,
+            zx: -q.y,
+        }
+    }
+}
+
+impl From<Vec3f32> for XrVector3 {
+    fn from(v: Vec3f32) -> Self {
+        Self {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<XrVector3> for Vec3f32 {
+    fn from(v: XrVector3) -> Self {
+        Vec3f32::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Pose3f32> for XrPose {
+    fn from(pose: Pose3f32) -> Self {
+        Self {
+            orientation: pose.rotation.into(),
+            position: pose.position.into(),
+        }
+    }
+}
+
+impl From<XrPose> for Pose3f32 {
+    fn from(pose: XrPose) -> Self {
+        Pose3f32::new(pose.position.into(), pose.orientation.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pose_conversions() {
+        // 90 degrees around +y as a quaternion turns +x into -z
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let q = XrQuaternion {
+            x: 0.0,
+            y: half,
+            z: 0.0,
+            w: half,
+        };
+        let rotation = Rot3Df32::from(q);
+        let v = rotation.rotated_vec(Vec3f32::new(1.0, 0.0, 0.0));
+        assert!((-0.0001..0.0001).contains(&v.distance(Vec3f32::new(0.0, 0.0, -1.0))));
+        assert_eq!(XrQuaternion::from(rotation), q);
+
+        let xr = XrPose {
+            orientation: q,
+            position: XrVector3 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+        };
+        let m = xr.to_mat4();
+        let p = m.transform_point(Vec3f32::new(1.0, 0.0, 0.0));
+        assert!((-0.0001..0.0001).contains(&p.distance(Vec3f32::new(1.0, 2.0, 2.0))));
+
+        let back = XrPose::from_mat4(&m);
+        let position = Vec3f32::from(back.position);
+        assert!((-0.0001..0.0001).contains(&position.distance(Vec3f32::new(1.0, 2.0, 3.0))));
+        let r = Rot3Df32::from(back.orientation);
+        assert!((0.9999..1.0001).contains(
+            &(r.s * rotation.s + r.xy * rotation.xy + r.yz * rotation.yz + r.zx * rotation.zx)
+                .abs()
+        ));
+    }
+}